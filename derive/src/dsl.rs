@@ -0,0 +1,185 @@
+//! Parser and expansion of the `permissions!` macro, a compact syntax for declaring a permission
+//! enum along with its implications and groups.
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::Token;
+
+/// The whole input of `permissions!`. The enum header is optional, without it the macro declares
+/// a private `enum Permissions`.
+pub(crate) struct Dsl {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    ident: syn::Ident,
+    variants: Punctuated<Variant, Token![,]>,
+}
+
+/// A single variant, `Name [implies A + B] [in group + other_group]`.
+struct Variant {
+    attrs: Vec<syn::Attribute>,
+    ident: syn::Ident,
+    implies: Vec<syn::Ident>,
+    groups: Vec<syn::Ident>,
+}
+
+impl Parse for Dsl {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse::<syn::Visibility>()?;
+
+        if input.peek(Token![enum]) {
+            input.parse::<Token![enum]>()?;
+            let ident = input.parse()?;
+            let content;
+            syn::braced!(content in input);
+            let variants = content.parse_terminated(Variant::parse)?;
+
+            return Ok(Self {
+                attrs,
+                vis,
+                ident,
+                variants,
+            });
+        }
+
+        if !matches!(vis, syn::Visibility::Inherited) {
+            return Err(input.error("expected `enum`"));
+        }
+
+        // Without a header the attributes parsed so far belong to the first variant.
+        let mut variants = Punctuated::new();
+        if !input.is_empty() {
+            variants.push_value(Variant::parse_with_attrs(input, attrs)?);
+            while !input.is_empty() {
+                variants.push_punct(input.parse()?);
+                if input.is_empty() {
+                    break;
+                }
+                variants.push_value(input.parse()?);
+            }
+        }
+
+        Ok(Self {
+            attrs: Vec::new(),
+            vis,
+            ident: syn::Ident::new("Permissions", proc_macro2::Span::call_site()),
+            variants,
+        })
+    }
+}
+
+impl Parse for Variant {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        Self::parse_with_attrs(input, attrs)
+    }
+}
+
+impl Variant {
+    fn parse_with_attrs(input: ParseStream, attrs: Vec<syn::Attribute>) -> syn::Result<Self> {
+        let ident = input.parse::<syn::Ident>()?;
+
+        let mut implies = Vec::new();
+        if input.peek(syn::Ident) && input.fork().parse::<syn::Ident>()? == "implies" {
+            input.parse::<syn::Ident>()?;
+            implies = parse_ident_list(input)?;
+        }
+
+        let mut groups = Vec::new();
+        if input.peek(Token![in]) {
+            input.parse::<Token![in]>()?;
+            groups = parse_ident_list(input)?;
+        }
+
+        if !input.is_empty() && !input.peek(Token![,]) {
+            return Err(input.error("expected `implies`, `in` or `,`"));
+        }
+
+        Ok(Self {
+            attrs,
+            ident,
+            implies,
+            groups,
+        })
+    }
+}
+
+/// Parses a non empty list of identifiers separated by `+`.
+fn parse_ident_list(input: ParseStream) -> syn::Result<Vec<syn::Ident>> {
+    let mut idents = vec![input.parse()?];
+    while input.peek(Token![+]) {
+        input.parse::<Token![+]>()?;
+        idents.push(input.parse()?);
+    }
+
+    Ok(idents)
+}
+
+impl Dsl {
+    /// Expands into the enum with the `Permissions` derive applied, the implications and groups
+    /// are turned into the `implies` and `perm` attributes understood by the derive.
+    ///
+    /// Variants disabled by `#[cfg(...)]` are removed before the derive sees the enum, thus an
+    /// implication of such a variant is wrapped in a `cfg_attr` with the same predicates.
+    pub(crate) fn expand(self) -> proc_macro2::TokenStream {
+        let Self {
+            attrs,
+            vis,
+            ident,
+            variants,
+        } = self;
+
+        // The `(...)` following `cfg` of each gated variant, keyed by the variant name.
+        let gates = variants
+            .iter()
+            .map(|x| {
+                let predicates = x
+                    .attrs
+                    .iter()
+                    .filter(|x| x.path.is_ident("cfg"))
+                    .map(|x| x.tokens.clone())
+                    .collect::<Vec<_>>();
+                (x.ident.clone(), predicates)
+            })
+            .filter(|(_, predicates)| !predicates.is_empty())
+            .collect::<Vec<_>>();
+
+        let variants = variants.into_iter().map(|x| {
+            let Variant {
+                attrs,
+                ident,
+                implies,
+                groups,
+            } = x;
+            let implies =
+                implies
+                    .iter()
+                    .map(|x| match gates.iter().find(|(ident, _)| ident == x) {
+                        Some((_, predicates)) => {
+                            quote! { #[cfg_attr(all(#(all #predicates),*), implies(#x))] }
+                        }
+                        None => quote! { #[implies(#x)] },
+                    });
+            let groups = groups.iter().map(|x| {
+                let group = x.to_string();
+                quote! { #[perm(group = #group)] }
+            });
+
+            quote! {
+                #(#attrs)*
+                #(#implies)*
+                #(#groups)*
+                #ident
+            }
+        });
+
+        quote! {
+            #[derive(type_permissions::Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+            #(#attrs)*
+            #vis enum #ident {
+                #(#variants,)*
+            }
+        }
+    }
+}
@@ -2,58 +2,1905 @@ use proc_macro::TokenStream;
 use quote::quote;
 use syn::{parse_macro_input, DeriveInput};
 
-#[proc_macro_derive(Permissions)]
+mod dsl;
+mod validate;
+
+#[proc_macro_derive(Permissions, attributes(permissions, implies, perm))]
 pub fn derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
+    let options = match Options::from_attrs(&input.attrs) {
+        Ok(x) => x,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
     let enum_fields = match input.data {
         syn::Data::Enum(x) => x.variants,
-        _ => {
-            return syn::Error::new_spanned(input, "expected enum")
-                .to_compile_error()
-                .into()
+        syn::Data::Struct(x) => return not_an_enum(x.struct_token, "struct"),
+        syn::Data::Union(x) => return not_an_enum(x.union_token, "union"),
+    };
+
+    if let Err(e) = check_unit_variants(&enum_fields).and(check_duplicates(&enum_fields, &options))
+    {
+        return e.to_compile_error().into();
+    }
+
+    let implied = match parse_implies(&enum_fields) {
+        Ok(x) => x,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let variant_options = match parse_variant_options(&enum_fields) {
+        Ok(x) => x,
+        Err(e) => return e.to_compile_error().into(),
+    };
+    let names = variant_options
+        .iter()
+        .map(|x| x.name.clone())
+        .collect::<Vec<_>>();
+
+    if let Some(f) = enum_fields.iter().nth(128) {
+        return syn::Error::new_spanned(
+            f,
+            "`Permissions` supports at most 128 variants as each one is assigned a bit in a `u128`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    if let Some(f) = enum_fields.iter().nth(64).filter(|_| options.bits) {
+        return syn::Error::new_spanned(
+            f,
+            "`#[permissions(bits)]` supports at most 64 variants as each one is assigned a bit in a `u64`",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let groups = match parse_groups(&enum_fields, &options, &variant_options) {
+        Ok(x) => x,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let target = Target {
+        ident: &input.ident,
+        vis: &input.vis,
+        generics: &input.generics,
+    };
+    // Items wrapped in `module` are one module further down, thus their visibility has to reach
+    // one module further up to stay accessible from where the enum is declared.
+    let (module_options, module_vis) = match options.module {
+        Some(_) => (
+            Options {
+                visibility: Some(nested_visibility(&options.visibility())),
+                ..options.clone()
+            },
+            nested_visibility(&input.vis),
+        ),
+        None => (options.clone(), input.vis.clone()),
+    };
+    let module_target = Target {
+        vis: &module_vis,
+        ..target
+    };
+
+    let traits = build_traits(&enum_fields, &module_target, &module_options, &implied);
+    let all = build_all(
+        &enum_fields,
+        &module_target,
+        &module_options,
+        &groups,
+        &implied,
+    );
+    let enum_impls = build_enum_impls(&enum_fields, &target, &variant_options);
+    let permission_set = build_permission_set(&module_target, &module_options);
+    let consts = build_consts(&enum_fields, &module_target, &module_options);
+    let serde = build_serde(&target, &names);
+    let registry = if options.registry {
+        build_registry(&enum_fields, &target, &variant_options)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    let iter = if options.iter {
+        build_iter(&enum_fields, &target)
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+    let bits = if options.bits {
+        match build_bits64(&enum_fields, &target) {
+            Ok(x) => x,
+            Err(e) => return e.to_compile_error().into(),
         }
+    } else {
+        proc_macro2::TokenStream::new()
     };
 
-    let traits = build_traits(&enum_fields, &input.ident);
+    let markers = enum_fields
+        .iter()
+        .map(|f| options.struct_name(&f.ident))
+        .chain(Some(options.all_name()))
+        .chain(groups.iter().map(|x| x.struct_name.clone()))
+        .collect::<Vec<_>>();
+    let markers = validate::build_markers_macro(&target, &markers);
 
-    let expanded = quote! {
+    let mut generated = quote! {
         #traits
+        #all
+        #permission_set
+        #consts
     };
+    if let Some(module) = &options.module {
+        let vis = options.visibility();
+        let doc = format!("Permission markers generated for `{}`.", target.ident);
+        generated = quote! {
+            #[doc = #doc]
+            #vis mod #module {
+                use super::*;
 
-    TokenStream::from(expanded)
+                #generated
+            }
+        };
+    }
+
+    let expanded = quote! {
+        #markers
+        #generated
+        #enum_impls
+        #serde
+        #registry
+        #iter
+        #bits
+    };
+
+    TokenStream::from(allow_lints(expanded))
+}
+
+/// Prepends an `allow` of the lints the generated items can trip to every top level item, the
+/// `module` wrapper passes it on to the items within. Marker and trait names are derived from the
+/// variant names, thus they dont follow the usual naming conventions if the variants dont, and
+/// the generated impls have no reason to follow the style lints of the crate deriving them.
+fn allow_lints(items: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    let items = syn::parse2::<syn::File>(items)
+        .expect("generated items are valid")
+        .items;
+    items
+        .iter()
+        .map(|item| {
+            quote! {
+                #[allow(
+                    non_camel_case_types,
+                    non_upper_case_globals,
+                    clippy::all,
+                    clippy::pedantic,
+                    clippy::nursery,
+                    clippy::missing_docs_in_private_items
+                )]
+                #item
+            }
+        })
+        .collect()
+}
+
+/// Options passed to the derive through `#[permissions(...)]` on the enum.
+#[derive(Default, Clone)]
+struct Options {
+    /// Prefix prepended to the names of the generated structs and traits.
+    prefix: String,
+    /// Visibility of the generated structs and traits, `pub` unless overridden.
+    visibility: Option<syn::Visibility>,
+    /// Whether to generate the `registry` function listing every permission.
+    registry: bool,
+    /// Whether to generate the `iter` function yielding every variant.
+    iter: bool,
+    /// Whether to generate the `u64` bitmask conversions for interop with stored bitmasks.
+    bits: bool,
+    /// Module the generated structs and traits are wrapped in, if any.
+    module: Option<syn::Ident>,
+}
+
+impl Options {
+    fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+
+        for attr in attrs.iter().filter(|x| x.path.is_ident("permissions")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(x) => x,
+                x => return Err(syn::Error::new_spanned(x, "expected `#[permissions(...)]`")),
+            };
+
+            for nested in list.nested.iter() {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("prefix") => {
+                        options.prefix = lit_str(&x.lit)?.value();
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(x)) if x.is_ident("registry") => {
+                        options.registry = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(x)) if x.is_ident("iter") => {
+                        options.iter = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::Path(x)) if x.is_ident("bits") => {
+                        options.bits = true;
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("module") => {
+                        options.module = Some(lit_str(&x.lit)?.parse()?);
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x))
+                        if x.path.is_ident("visibility") =>
+                    {
+                        options.visibility = Some(lit_str(&x.lit)?.parse()?);
+                    }
+                    x => return Err(syn::Error::new_spanned(x, "unknown `permissions` option")),
+                }
+            }
+        }
+
+        Ok(options)
+    }
+
+    /// Returns the visibility of the generated items.
+    fn visibility(&self) -> syn::Visibility {
+        self.visibility
+            .clone()
+            .unwrap_or_else(|| syn::parse_quote!(pub))
+    }
+
+    /// Returns the name of the struct generated for `variant`.
+    fn struct_name(&self, variant: &syn::Ident) -> syn::Ident {
+        syn::Ident::new(&format!("{}{}", self.prefix, variant), variant.span())
+    }
+
+    /// Returns the name of the struct which carries every permission.
+    fn all_name(&self) -> syn::Ident {
+        syn::Ident::new(
+            &format!("{}All", self.prefix),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    /// Returns the name of the generated role set newtype.
+    fn set_name(&self) -> syn::Ident {
+        syn::Ident::new(
+            &format!("{}PermissionSet", self.prefix),
+            proc_macro2::Span::call_site(),
+        )
+    }
+
+    /// Returns the name of the trait generated for `variant`.
+    fn trait_name(&self, variant: &syn::Ident) -> syn::Ident {
+        syn::Ident::new(&format!("T{}{}", self.prefix, variant), variant.span())
+    }
+
+    /// Returns the name of the marker generated for the group named `group` in pascal case.
+    fn group_struct_name(&self, group: &str, span: proc_macro2::Span) -> syn::Ident {
+        syn::Ident::new(&format!("{}{}Group", self.prefix, group), span)
+    }
+
+    /// Returns the name of the trait generated for the group named `group` in pascal case.
+    fn group_trait_name(&self, group: &str, span: proc_macro2::Span) -> syn::Ident {
+        syn::Ident::new(&format!("T{}{}Group", self.prefix, group), span)
+    }
+
+    /// Returns the name of the shorthand bound generated for `variant`, see `build_has_trait`.
+    fn has_name(&self, variant: &syn::Ident) -> syn::Ident {
+        syn::Ident::new(&format!("Has{}{}", self.prefix, variant), variant.span())
+    }
+}
+
+/// Returns the visibility which, from inside a child module, grants the same access as `vis` does
+/// from its parent.
+fn nested_visibility(vis: &syn::Visibility) -> syn::Visibility {
+    match vis {
+        syn::Visibility::Inherited => syn::parse_quote!(pub(super)),
+        syn::Visibility::Restricted(x) if x.path.is_ident("self") => syn::parse_quote!(pub(super)),
+        syn::Visibility::Restricted(x) if x.path.segments[0].ident == "super" => {
+            let path = &x.path;
+            syn::parse_quote!(pub(in super::#path))
+        }
+        x => x.clone(),
+    }
+}
+
+fn lit_str(lit: &syn::Lit) -> syn::Result<&syn::LitStr> {
+    match lit {
+        syn::Lit::Str(x) => Ok(x),
+        x => Err(syn::Error::new_spanned(x, "expected a string literal")),
+    }
+}
+
+/// Builds the error emitted when `Permissions` is derived on a struct or union.
+fn not_an_enum(keyword: impl quote::ToTokens, kind: &str) -> TokenStream {
+    syn::Error::new_spanned(
+        keyword,
+        format!(
+            "`Permissions` can only be derived on enums with unit variants; found a {}",
+            kind
+        ),
+    )
+    .to_compile_error()
+    .into()
+}
+
+/// Data carrying variants cant be supported as `Dispatch::dispatch` has no access to a value of
+/// the permission struct, so the payload could never factor into permission checks. The only
+/// exception is a single `PhantomData`, which generic enums need to use their parameters.
+fn check_unit_variants(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> syn::Result<()> {
+    let mut errors = fields
+        .iter()
+        .filter(|f| !matches!(f.fields, syn::Fields::Unit) && !is_phantom_variant(f))
+        .map(|f| {
+            syn::Error::new_spanned(
+                &f.fields,
+                format!(
+                    "`Permissions` only supports unit variants, `{}` carries data which cant be \
+                     checked at the type level",
+                    f.ident
+                ),
+            )
+        });
+
+    match errors.next() {
+        Some(mut error) => {
+            errors.for_each(|e| error.combine(e));
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Returns whether `variant` is a tuple variant carrying nothing but a `PhantomData`.
+fn is_phantom_variant(variant: &syn::Variant) -> bool {
+    let fields = match &variant.fields {
+        syn::Fields::Unnamed(x) if x.unnamed.len() == 1 => &x.unnamed[0],
+        _ => return false,
+    };
+
+    match &fields.ty {
+        syn::Type::Path(x) => x
+            .path
+            .segments
+            .last()
+            .is_some_and(|x| x.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// Every variant generates a struct named after it and a trait prefixed with `T`. Two variants
+/// generating the same name would otherwise fail far away from the source with a "defined
+/// multiple times" error on the generated items.
+fn check_duplicates(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    options: &Options,
+) -> syn::Result<()> {
+    let mut seen = std::collections::HashMap::new();
+
+    for f in fields.iter() {
+        let names = [
+            options.struct_name(&f.ident),
+            options.trait_name(&f.ident),
+            options.has_name(&f.ident),
+        ];
+        for generated in [options.all_name(), options.set_name()].iter() {
+            if names.contains(generated) {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!(
+                        "permission variant `{}` clashes with the generated `{}` struct",
+                        f.ident, generated
+                    ),
+                ));
+            }
+        }
+
+        for name in names.iter().map(|x| x.to_string()) {
+            if let Some(first) = seen.insert(name.clone(), &f.ident) {
+                let message = if first == &f.ident {
+                    format!("duplicate permission variant `{}`", f.ident)
+                } else {
+                    format!(
+                        "permission variant `{}` generates `{}` which clashes with the items \
+                         generated for `{}`",
+                        f.ident, name, first
+                    )
+                };
+                return Err(syn::Error::new_spanned(&f.ident, message));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the `#[implies(...)]` attributes on the variants. Returns, for every variant, the
+/// indices of the variants it transitively implies, excluding itself.
+fn parse_implies(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> syn::Result<Vec<Vec<usize>>> {
+    let mut direct = Vec::with_capacity(fields.len());
+
+    for f in fields.iter() {
+        let mut implies = Vec::new();
+
+        for attr in f.attrs.iter().filter(|x| x.path.is_ident("implies")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(x) if !x.nested.is_empty() => x,
+                x => {
+                    return Err(syn::Error::new_spanned(
+                        x,
+                        "expected `#[implies(...)]` with at least one permission variant",
+                    ))
+                }
+            };
+
+            for nested in list.nested.iter() {
+                let idx = match nested {
+                    syn::NestedMeta::Meta(syn::Meta::Path(x)) => {
+                        fields.iter().position(|f| x.is_ident(&f.ident))
+                    }
+                    _ => None,
+                };
+
+                match idx {
+                    Some(idx) => implies.push(idx),
+                    None => {
+                        return Err(syn::Error::new_spanned(
+                            nested,
+                            "expected the name of a permission variant",
+                        ))
+                    }
+                }
+            }
+        }
+
+        direct.push(implies);
+    }
+
+    let mut closures = Vec::with_capacity(fields.len());
+    for (idx, f) in fields.iter().enumerate() {
+        let mut closure = Vec::new();
+        let mut stack = direct[idx].clone();
+
+        while let Some(next) = stack.pop() {
+            if next == idx {
+                return Err(syn::Error::new_spanned(
+                    &f.ident,
+                    format!(
+                        "permission variant `{}` implies itself through a cycle of `implies`",
+                        f.ident
+                    ),
+                ));
+            }
+
+            if !closure.contains(&next) {
+                closure.push(next);
+                stack.extend(direct[next].iter().copied());
+            }
+        }
+
+        closure.sort_unstable();
+        closures.push(closure);
+    }
+
+    Ok(closures)
+}
+
+/// Options passed to the derive through `#[perm(...)]` on a variant.
+struct VariantOptions {
+    /// The string the variant is displayed and parsed as. This is the variant name unless
+    /// overridden with `rename`, which leaves the generated items untouched.
+    name: String,
+    /// Description of the permission listed in the registry.
+    doc: String,
+    /// Groups the variant was added to with `group`, see `parse_groups`.
+    groups: Vec<syn::LitStr>,
+    /// Additional strings the variant is parsed from, set with `alias`. Unlike `name` these are
+    /// never displayed, which eases migrating away from legacy scope names.
+    aliases: Vec<syn::LitStr>,
+}
+
+fn parse_variant_options(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> syn::Result<Vec<VariantOptions>> {
+    let mut variants = Vec::<VariantOptions>::with_capacity(fields.len());
+
+    for f in fields.iter() {
+        let mut options = VariantOptions {
+            name: f.ident.to_string(),
+            doc: String::new(),
+            groups: Vec::new(),
+            aliases: Vec::new(),
+        };
+        let mut rename = None;
+
+        for attr in f.attrs.iter().filter(|x| x.path.is_ident("perm")) {
+            let list = match attr.parse_meta()? {
+                syn::Meta::List(x) => x,
+                x => return Err(syn::Error::new_spanned(x, "expected `#[perm(...)]`")),
+            };
+
+            for nested in list.nested.iter() {
+                match nested {
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("rename") => {
+                        let lit = lit_str(&x.lit)?;
+                        options.name = lit.value();
+                        rename = Some(lit.clone());
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("doc") => {
+                        options.doc = lit_str(&x.lit)?.value();
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("group") => {
+                        options.groups.push(lit_str(&x.lit)?.clone());
+                    }
+                    syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("alias") => {
+                        options.aliases.push(lit_str(&x.lit)?.clone());
+                    }
+                    x => return Err(syn::Error::new_spanned(x, "unknown `perm` option")),
+                }
+            }
+        }
+
+        if variants.iter().any(|x| x.name == options.name) {
+            let message = format!("permission name `{}` is used more than once", options.name);
+            return Err(match rename {
+                Some(lit) => syn::Error::new_spanned(lit, message),
+                None => syn::Error::new_spanned(&f.ident, message),
+            });
+        }
+
+        variants.push(options);
+    }
+
+    let mut seen = variants.iter().map(|x| x.name.clone()).collect::<Vec<_>>();
+    for alias in variants.iter().flat_map(|x| x.aliases.iter()) {
+        if seen.contains(&alias.value()) {
+            let message = format!("permission name `{}` is used more than once", alias.value());
+            return Err(syn::Error::new_spanned(alias, message));
+        }
+        seen.push(alias.value());
+    }
+
+    Ok(variants)
+}
+
+/// A group of permissions declared through `#[perm(group = "...")]`, which generates a marker
+/// carrying every member of the group.
+struct Group {
+    /// Name of the generated marker struct.
+    struct_name: syn::Ident,
+    /// Name of the generated trait, implemented by the marker and `All`.
+    trait_name: syn::Ident,
+    /// Indices of the member variants in declaration order.
+    members: Vec<usize>,
+}
+
+/// Collects the groups declared on the variants in order of first appearance. Group names are
+/// converted to pascal case, thus `"billing"` generates `BillingGroup` and `TBillingGroup`.
+fn parse_groups(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    options: &Options,
+    variant_options: &[VariantOptions],
+) -> syn::Result<Vec<Group>> {
+    let mut groups = Vec::<Group>::new();
+
+    for (idx, lit) in variant_options
+        .iter()
+        .enumerate()
+        .flat_map(|(idx, x)| x.groups.iter().map(move |lit| (idx, lit)))
+    {
+        // `Ident::new` panics on invalid identifiers, thus the name is validated up front.
+        let name = pascal_case(&lit.value());
+        if name.is_empty() || syn::parse_str::<syn::Ident>(&format!("{}Group", name)).is_err() {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!("invalid permission group name `{}`", lit.value()),
+            ));
+        }
+        let struct_name = options.group_struct_name(&name, lit.span());
+        let trait_name = options.group_trait_name(&name, lit.span());
+
+        if let Some(group) = groups.iter_mut().find(|x| x.struct_name == struct_name) {
+            if !group.members.contains(&idx) {
+                group.members.push(idx);
+            }
+            continue;
+        }
+
+        let clash = fields.iter().find(|f| {
+            let names = [
+                options.struct_name(&f.ident),
+                options.trait_name(&f.ident),
+                options.has_name(&f.ident),
+            ];
+            names.contains(&struct_name) || names.contains(&trait_name)
+        });
+        if let Some(f) = clash {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!(
+                    "permission group `{}` generates `{}` which clashes with the items generated \
+                     for `{}`",
+                    lit.value(),
+                    struct_name,
+                    f.ident
+                ),
+            ));
+        }
+        if struct_name == options.all_name() {
+            return Err(syn::Error::new_spanned(
+                lit,
+                format!(
+                    "permission group `{}` clashes with the generated `{}` struct",
+                    lit.value(),
+                    struct_name
+                ),
+            ));
+        }
+
+        groups.push(Group {
+            struct_name,
+            trait_name,
+            members: vec![idx],
+        });
+    }
+
+    Ok(groups)
+}
+
+/// Converts a group name like `"billing"` or `"user-admin"` to pascal case.
+fn pascal_case(name: &str) -> String {
+    name.split(|c: char| c == '_' || c == '-' || c.is_whitespace())
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            let mut chars = x.chars();
+            chars
+                .next()
+                .map(|c| c.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Converts a name like `CanRead` or `HTTPGet` to screaming snake case, `CAN_READ` and `HTTP_GET`.
+fn screaming_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut out = String::new();
+
+    for (idx, c) in chars.iter().enumerate() {
+        let prev = idx.checked_sub(1).map(|x| chars[x]);
+        let next = chars.get(idx + 1);
+        let boundary = c.is_uppercase()
+            && prev.is_some_and(|x| {
+                x.is_lowercase()
+                    || x.is_ascii_digit()
+                    || (x.is_uppercase() && next.is_some_and(|x| x.is_lowercase()))
+            });
+
+        if boundary && !out.ends_with('_') {
+            out.push('_');
+        }
+        out.extend(c.to_uppercase());
+    }
+
+    out
+}
+
+/// The enum `Permissions` is derived on, bundling what is needed to name it in the generated
+/// impls.
+struct Target<'a> {
+    ident: &'a syn::Ident,
+    vis: &'a syn::Visibility,
+    generics: &'a syn::Generics,
+}
+
+impl Target<'_> {
+    /// Returns the enum type including its generic parameters.
+    fn ty(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote! { #ident #ty_generics }
+    }
+
+    /// Returns the where clause of the enum extended with `bounds` on the enum type itself, which
+    /// for generic enums carries the bounds the library traits put on the permission enum.
+    fn where_with(&self, bounds: proc_macro2::TokenStream) -> syn::WhereClause {
+        let ty = self.ty();
+        let mut generics = self.generics.clone();
+        generics
+            .make_where_clause()
+            .predicates
+            .push(syn::parse_quote!(#ty: #bounds));
+        generics
+            .where_clause
+            .expect("where clause was just created")
+    }
+
+    /// Returns the expression constructing `variant`.
+    fn ctor(&self, variant: &syn::Variant) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        let name = &variant.ident;
+        match variant.fields {
+            syn::Fields::Unit => quote! { #ident::#name },
+            _ => quote! { #ident::#name(core::marker::PhantomData) },
+        }
+    }
+
+    /// Returns the pattern matching `variant`.
+    fn pat(&self, variant: &syn::Variant) -> proc_macro2::TokenStream {
+        let ident = self.ident;
+        let name = &variant.ident;
+        match variant.fields {
+            syn::Fields::Unit => quote! { #ident::#name },
+            _ => quote! { #ident::#name(..) },
+        }
+    }
 }
 
 fn build_traits(
     fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
-    enum_ident: &proc_macro2::Ident,
+    target: &Target,
+    options: &Options,
+    implied: &[Vec<usize>],
 ) -> proc_macro2::TokenStream {
     let mut tts = Vec::new();
+    let (impl_generics, _, base_where) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let where_clause = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+
+    for (idx, f) in fields.iter().enumerate() {
+        let bit = 1u128 << idx;
+        let trait_name = options.trait_name(&f.ident);
+        let struct_name = options.struct_name(&f.ident);
+        let variant = target.ctor(f);
+        let vis = options.visibility();
+
+        // A role set satisfies this permission if it holds the variant itself or any variant
+        // which transitively implies it.
+        let satisfied_by = (0..fields.len())
+            .filter(|x| *x == idx || implied[*x].contains(&idx))
+            .collect::<Vec<_>>();
+        let satisfied_mask = satisfied_mask(idx, implied);
+        let satisfied_by = satisfied_by
+            .iter()
+            .map(|x| target.ctor(&fields[*x]))
+            .collect::<Vec<_>>();
+
+        let implied_traits = implied[idx]
+            .iter()
+            .map(|x| options.trait_name(&fields[*x].ident));
+        let implied_structs = implied[idx]
+            .iter()
+            .map(|x| options.struct_name(&fields[*x].ident));
+
+        let cached = build_cached(&struct_name, &trait_name, target);
+        let has_trait = build_has_trait(&trait_name, &options.has_name(&f.ident), target, &vis);
+        let collect_small = build_collect_small(target, std::slice::from_ref(&variant));
+        let name = struct_name.to_string();
+        let trait_doc = format!(
+            "Bound satisfied by tokens carrying `{}::{}`, `PhantomToken<{}>` or any permission \
+             implying it.",
+            target.ident, f.ident, struct_name
+        );
+        let struct_doc = format!("Permission marker for `{}::{}`.", target.ident, f.ident);
 
-    for f in fields.iter() {
-        let trait_name = syn::Ident::new(&format!("T{}", f.ident.clone()), f.ident.span());
-        let struct_name = f.ident.clone();
-        let enum_name = enum_ident.clone();
         tts.push(quote! {
-            pub trait #trait_name {}
-            pub struct #struct_name;
+            #[doc = #trait_doc]
+            #vis trait #trait_name {}
+            #[doc = #struct_doc]
+            #vis struct #struct_name;
             impl #trait_name for #struct_name {}
-            impl type_permissions::Dispatch<#enum_name> for #struct_name {
-                fn dispatch() -> std::collections::HashSet<#enum_name> {
-                    let mut set = std::collections::HashSet::new();
-                    set.insert(#enum_name::#struct_name);
+            #(impl #implied_traits for #struct_name {})*
+            #(#[allow(unsafe_code)]
+              unsafe impl type_permissions::__private::ImpliesSealed<#implied_structs, type_permissions::implies::Here> for #struct_name {}
+              impl type_permissions::Implies<#implied_structs, type_permissions::implies::Here> for #struct_name {})*
+            impl #impl_generics type_permissions::Dispatch<#enum_ty> for #struct_name #where_clause {
+                fn dispatch() -> type_permissions::HashSet<#enum_ty> {
+                    let mut set = type_permissions::HashSet::new();
+                    set.insert(#variant);
                     set
                 }
+
+                fn collect_into(set: &mut type_permissions::HashSet<#enum_ty>) {
+                    set.insert(#variant);
+                }
+
+                #collect_small
+
+                fn check_match(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                    #(ops.contains(&#satisfied_by))||*
+                }
             }
-            impl type_permissions::Dispatch<#enum_name> for dyn #trait_name {
-                fn dispatch() -> std::collections::HashSet<#enum_name> {
-                    let mut set = std::collections::HashSet::new();
-                    set.insert(#enum_name::#struct_name);
+            impl #impl_generics type_permissions::Dispatch<#enum_ty> for dyn #trait_name #where_clause {
+                fn dispatch() -> type_permissions::HashSet<#enum_ty> {
+                    let mut set = type_permissions::HashSet::new();
+                    set.insert(#variant);
                     set
                 }
+
+                fn collect_into(set: &mut type_permissions::HashSet<#enum_ty>) {
+                    set.insert(#variant);
+                }
+
+                #collect_small
+
+                fn check_match(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                    <#struct_name as type_permissions::Dispatch<#enum_ty>>::check_match(ops)
+                }
+            }
+            #cached
+            #has_trait
+            impl #impl_generics core::convert::From<#struct_name> for #enum_ty #base_where {
+                fn from(_: #struct_name) -> Self {
+                    #variant
+                }
+            }
+            impl #struct_name {
+                /// Name of this permission, see `PermName`.
+                pub const NAME: &'static str = #name;
+            }
+            impl type_permissions::__private::PermNameSealed for #struct_name {}
+            impl type_permissions::PermName for #struct_name {
+                fn name() -> type_permissions::__private::String {
+                    Self::NAME.into()
+                }
+            }
+            impl type_permissions::__private::PermNameSealed for dyn #trait_name {}
+            impl type_permissions::PermName for dyn #trait_name {
+                fn name() -> type_permissions::__private::String {
+                    #struct_name::NAME.into()
+                }
+            }
+            impl #impl_generics type_permissions::Erasable<#enum_ty> for #struct_name #where_clause {}
+            impl #impl_generics type_permissions::Erasable<#enum_ty> for dyn #trait_name #where_clause {}
+            impl<const ROLES: u128> type_permissions::ConstCheck<ROLES> for #struct_name {
+                const OK: bool = ROLES & #satisfied_mask != 0;
+            }
+            impl<const ROLES: u128> type_permissions::ConstCheck<ROLES> for dyn #trait_name {
+                const OK: bool = ROLES & #satisfied_mask != 0;
+            }
+            impl #impl_generics type_permissions::DispatchBits<#enum_ty> for #struct_name #where_clause {
+                fn bits() -> u128 {
+                    #bit
+                }
+
+                fn check_bits(ops: u128) -> bool {
+                    ops & #satisfied_mask != 0
+                }
+            }
+            impl #impl_generics type_permissions::DispatchBits<#enum_ty> for dyn #trait_name #where_clause {
+                fn bits() -> u128 {
+                    #bit
+                }
+
+                fn check_bits(ops: u128) -> bool {
+                    ops & #satisfied_mask != 0
+                }
             }
         });
     }
 
     quote! { #(#tts)* }
 }
+
+/// Returns the bitmask of the variants satisfying the variant at `idx`, the variant itself and
+/// every variant which transitively implies it.
+fn satisfied_mask(idx: usize, implied: &[Vec<usize>]) -> u128 {
+    (0..implied.len())
+        .filter(|x| *x == idx || implied[*x].contains(&idx))
+        .fold(0u128, |acc, x| acc | 1u128 << x)
+}
+
+/// Builds the `CachedDispatch` impls for a single permission. The cache is backed by
+/// `std::sync::OnceLock`, thus these are left out when building for `no_std`. Statics cant be
+/// generic, thus generic enums fall back to the global cache of the default implementation.
+#[cfg(not(feature = "no_std"))]
+fn build_cached(
+    struct_name: &syn::Ident,
+    trait_name: &syn::Ident,
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, _) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let where_clause =
+        target.where_with(quote! { core::hash::Hash + core::cmp::Eq + Send + Sync + 'static });
+    let cached_dispatch = if target.generics.params.is_empty() {
+        quote! {
+            fn cached_dispatch() -> &'static type_permissions::HashSet<#enum_ty> {
+                static CACHE: std::sync::OnceLock<type_permissions::HashSet<#enum_ty>> =
+                    std::sync::OnceLock::new();
+                CACHE.get_or_init(<Self as type_permissions::Dispatch<#enum_ty>>::dispatch)
+            }
+        }
+    } else {
+        proc_macro2::TokenStream::new()
+    };
+
+    quote! {
+        impl #impl_generics type_permissions::CachedDispatch<#enum_ty> for #struct_name #where_clause {
+            #cached_dispatch
+
+            fn check_match_cached(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                <Self as type_permissions::Dispatch<#enum_ty>>::check_match(ops)
+            }
+        }
+        impl #impl_generics type_permissions::CachedDispatch<#enum_ty> for dyn #trait_name #where_clause {
+            fn check_match_cached(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                <#struct_name as type_permissions::Dispatch<#enum_ty>>::check_match(ops)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "no_std")]
+fn build_cached(_: &syn::Ident, _: &syn::Ident, _: &Target) -> proc_macro2::TokenStream {
+    proc_macro2::TokenStream::new()
+}
+
+/// Builds the `Has*` trait of a single permission, a shorthand for bounding on both the permission
+/// trait and `Dispatch` which is blanket implemented for every type satisfying the two.
+fn build_has_trait(
+    trait_name: &syn::Ident,
+    has_name: &syn::Ident,
+    target: &Target,
+    vis: &syn::Visibility,
+) -> proc_macro2::TokenStream {
+    let enum_ty = target.ty();
+    let (_, ty_generics, _) = target.generics.split_for_impl();
+    let where_clause = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+    let mut trait_generics = target.generics.clone();
+    trait_generics.where_clause = None;
+    let mut impl_generics = trait_generics.clone();
+    impl_generics.params.insert(
+        0,
+        syn::parse_quote!(__T: ?Sized + #trait_name + type_permissions::Dispatch<#enum_ty>),
+    );
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    let doc = format!(
+        "Shorthand for bounding on both `{}` and `Dispatch<{}>`.",
+        trait_name, target.ident
+    );
+
+    quote! {
+        #[doc = #doc]
+        #vis trait #has_name #trait_generics: #trait_name + type_permissions::Dispatch<#enum_ty> #where_clause {}
+        impl #impl_generics #has_name #ty_generics for __T #where_clause {}
+    }
+}
+
+/// Builds the `Dispatch::collect_small` override inserting `variants`, which only exists with the
+/// `small` feature enabled.
+fn build_collect_small(
+    target: &Target,
+    variants: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    if !cfg!(feature = "small") {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let enum_ty = target.ty();
+    quote! {
+        fn collect_small(set: &mut type_permissions::__private::SmallSet<#enum_ty>) {
+            #(set.insert(#variants);)*
+        }
+    }
+}
+
+/// Builds the struct which carries every permission of the enum at once, along with the markers
+/// of the declared groups.
+fn build_all(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+    options: &Options,
+    groups: &[Group],
+    implied: &[Vec<usize>],
+) -> proc_macro2::TokenStream {
+    let all_name = options.all_name();
+    let vis = options.visibility();
+    let doc = format!(
+        "Permission which carries every variant of `{}`. A token of this type satisfies any \
+         single permission bound, thus deriving it requires a role set satisfying every single \
+         permission.",
+        target.ident
+    );
+    let all = build_composite(
+        fields,
+        &(0..fields.len()).collect::<Vec<_>>(),
+        &all_name,
+        &doc,
+        target,
+        options,
+        implied,
+    );
+
+    let groups = groups.iter().map(|group| {
+        let struct_name = &group.struct_name;
+        let trait_name = &group.trait_name;
+        let members = group
+            .members
+            .iter()
+            .map(|x| format!("`{}`", fields[*x].ident))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let doc = format!(
+            "Permission which carries every variant of the group: {}. Deriving a token of this \
+             type requires a role set satisfying every member.",
+            members
+        );
+        let composite = build_composite(
+            fields,
+            &group.members,
+            struct_name,
+            &doc,
+            target,
+            options,
+            implied,
+        );
+        let trait_doc = format!(
+            "Bound satisfied by tokens carrying every member of the group: {}.",
+            members
+        );
+
+        quote! {
+            #composite
+            #[doc = #trait_doc]
+            #vis trait #trait_name {}
+            impl #trait_name for #struct_name {}
+            impl #trait_name for #all_name {}
+            #[allow(unsafe_code)]
+            unsafe impl type_permissions::__private::ImpliesSealed<#struct_name, type_permissions::implies::Here> for #all_name {}
+            impl type_permissions::Implies<#struct_name, type_permissions::implies::Here> for #all_name {}
+        }
+    });
+
+    quote! {
+        #all
+        #(#groups)*
+    }
+}
+
+/// Builds a marker struct named `name` which carries the variants at the indices `members`, it
+/// satisfies the bounds of each member and requires all of them to be derived. Like a chain of
+/// `And`, a role set satisfies it if it satisfies every member, either directly or through a
+/// variant implying the member.
+fn build_composite(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    members: &[usize],
+    name: &syn::Ident,
+    doc: &str,
+    target: &Target,
+    options: &Options,
+    implied: &[Vec<usize>],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, _) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let where_clause = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+    let vis = options.visibility();
+    let trait_names = members
+        .iter()
+        .map(|x| options.trait_name(&fields[*x].ident))
+        .collect::<Vec<_>>();
+    let struct_names = members
+        .iter()
+        .map(|x| options.struct_name(&fields[*x].ident))
+        .collect::<Vec<_>>();
+    let variants = members
+        .iter()
+        .map(|x| target.ctor(&fields[*x]))
+        .collect::<Vec<_>>();
+    let collect_small = build_collect_small(target, &variants);
+    let name_str = name.to_string();
+    let bits = members.iter().fold(0u128, |acc, x| acc | 1u128 << x);
+    let satisfied_masks = members
+        .iter()
+        .map(|x| satisfied_mask(*x, implied))
+        .collect::<Vec<_>>();
+    let cached = if cfg!(feature = "no_std") {
+        quote! {}
+    } else {
+        let where_clause =
+            target.where_with(quote! { core::hash::Hash + core::cmp::Eq + Send + Sync + 'static });
+        quote! {
+            impl #impl_generics type_permissions::CachedDispatch<#enum_ty> for #name #where_clause {
+                fn check_match_cached(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                    <Self as type_permissions::Dispatch<#enum_ty>>::check_match(ops)
+                }
+            }
+        }
+    };
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #name;
+        #(impl #trait_names for #name {})*
+        #(#[allow(unsafe_code)]
+          unsafe impl type_permissions::__private::ImpliesSealed<#struct_names, type_permissions::implies::Here> for #name {}
+          impl type_permissions::Implies<#struct_names, type_permissions::implies::Here> for #name {})*
+        impl #impl_generics type_permissions::Dispatch<#enum_ty> for #name #where_clause {
+            fn dispatch() -> type_permissions::HashSet<#enum_ty> {
+                let mut set = type_permissions::HashSet::new();
+                Self::collect_into(&mut set);
+                set
+            }
+
+            fn collect_into(set: &mut type_permissions::HashSet<#enum_ty>) {
+                #(set.insert(#variants);)*
+            }
+
+            #collect_small
+
+            fn check_match(ops: &type_permissions::HashSet<#enum_ty>) -> bool {
+                true #(&& <#struct_names as type_permissions::Dispatch<#enum_ty>>::check_match(ops))*
+            }
+        }
+        #cached
+        impl #name {
+            /// Name of this permission, see `PermName`.
+            pub const NAME: &'static str = #name_str;
+        }
+        impl type_permissions::__private::PermNameSealed for #name {}
+        impl type_permissions::PermName for #name {
+            fn name() -> type_permissions::__private::String {
+                Self::NAME.into()
+            }
+        }
+        impl #impl_generics type_permissions::Erasable<#enum_ty> for #name #where_clause {}
+        impl<const ROLES: u128> type_permissions::ConstCheck<ROLES> for #name {
+            const OK: bool = true #(&& ROLES & #satisfied_masks != 0)*;
+        }
+        impl #impl_generics type_permissions::DispatchBits<#enum_ty> for #name #where_clause {
+            fn bits() -> u128 {
+                #bits
+            }
+
+            fn check_bits(ops: u128) -> bool {
+                true #(&& ops & #satisfied_masks != 0)*
+            }
+        }
+    }
+}
+
+/// Builds a `const` for each variant named after its marker in screaming snake case, such that
+/// role sets can be built without naming the enum. Generic enums are skipped as `const` items
+/// cant be generic, as are variants already named in screaming case, whose `const` would clash with
+/// the unit marker struct of the same name.
+fn build_consts(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    if !target.generics.params.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
+    let enum_ty = target.ty();
+    let vis = target.vis;
+    let consts = fields.iter().filter_map(|f| {
+        let struct_name = options.struct_name(&f.ident);
+        let name = screaming_snake_case(&struct_name.to_string());
+        if struct_name == name {
+            return None;
+        }
+
+        let name = syn::Ident::new(&name, struct_name.span());
+        let variant = target.ctor(f);
+        let doc = format!("Shorthand for `{}::{}`.", target.ident, f.ident);
+
+        Some(quote! {
+            #[doc = #doc]
+            #vis const #name: #enum_ty = #variant;
+        })
+    });
+
+    quote! { #(#consts)* }
+}
+
+/// Builds the `PermissionSet` newtype, a role set of the enum with a few fluent helpers. As its
+/// `Deref` impl exposes the enum the set takes the visibility of the enum rather than the one of
+/// the generated permission items.
+fn build_permission_set(target: &Target, options: &Options) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, _) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let where_clause = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+    let generics = target.generics;
+    let set_name = options.set_name();
+    let vis = target.vis;
+    let doc = format!(
+        "Set of `{}` permissions held by a user. Derefs to the underlying `HashSet`, thus it can \
+         be passed to `Dispatch::try_into_token` as is.",
+        target.ident
+    );
+
+    quote! {
+        #[doc = #doc]
+        #vis struct #set_name #generics (type_permissions::HashSet<#enum_ty>) #where_clause;
+
+        impl #impl_generics #set_name #ty_generics #where_clause {
+            /// Creates an empty set.
+            pub fn new() -> Self {
+                Self(type_permissions::HashSet::new())
+            }
+
+            /// Adds `perm` to the set, returning the set for chaining.
+            pub fn insert_perm(mut self, perm: #enum_ty) -> Self {
+                self.0.insert(perm);
+                self
+            }
+
+            /// Returns whether the set holds `perm`.
+            pub fn contains(&self, perm: &#enum_ty) -> bool {
+                self.0.contains(perm)
+            }
+
+            /// Returns the union of this set and `other`.
+            pub fn union_with(mut self, other: Self) -> Self {
+                self.0.extend(other.0);
+                self
+            }
+
+            /// Derives a token of type `T` if the set satisfies it.
+            pub fn try_into<T>(&self) -> Option<type_permissions::PhantomToken<T>>
+            where
+                T: ?Sized + type_permissions::Dispatch<#enum_ty>,
+            {
+                T::try_into_token(&self.0)
+            }
+
+            /// Returns the underlying set.
+            pub fn into_inner(self) -> type_permissions::HashSet<#enum_ty> {
+                self.0
+            }
+        }
+
+        impl #impl_generics core::default::Default for #set_name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl #impl_generics core::cmp::PartialEq for #set_name #ty_generics #where_clause {
+            fn eq(&self, other: &Self) -> bool {
+                self.0 == other.0
+            }
+        }
+
+        impl #impl_generics core::cmp::Eq for #set_name #ty_generics #where_clause {}
+
+        impl #impl_generics core::ops::Deref for #set_name #ty_generics #where_clause {
+            type Target = type_permissions::HashSet<#enum_ty>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl #impl_generics core::convert::From<type_permissions::HashSet<#enum_ty>> for #set_name #ty_generics #where_clause {
+            fn from(set: type_permissions::HashSet<#enum_ty>) -> Self {
+                Self(set)
+            }
+        }
+
+        impl #impl_generics core::iter::FromIterator<#enum_ty> for #set_name #ty_generics #where_clause {
+            fn from_iter<I: core::iter::IntoIterator<Item = #enum_ty>>(iter: I) -> Self {
+                Self(iter.into_iter().collect())
+            }
+        }
+    }
+}
+
+/// Builds the inherent items and std trait impls on the permission enum itself.
+fn build_enum_impls(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+    variant_options: &[VariantOptions],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let names = variant_options.iter().map(|x| &x.name).collect::<Vec<_>>();
+    let aliases = variant_options.iter().map(|x| &x.aliases);
+    let bits_where = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+    let static_where = target.where_with(quote! { 'static });
+    let variants = fields.iter().map(|f| target.ctor(f)).collect::<Vec<_>>();
+    let pats = fields.iter().map(|f| target.pat(f)).collect::<Vec<_>>();
+    let bits = (0..variants.len()).map(|x| 1u128 << x);
+    let indices = 0..variants.len();
+    let count = variants.len();
+
+    quote! {
+        impl #impl_generics type_permissions::PermissionBits for #enum_ty #bits_where {
+            fn to_bits(&self) -> u128 {
+                self.bit()
+            }
+        }
+
+        impl #impl_generics #enum_ty #where_clause {
+            /// Number of permission variants, the length of arrays indexed by `index`.
+            pub const PERMISSION_COUNT: usize = #count;
+
+            /// Returns the index of this variant in declaration order, which is stable and
+            /// contiguous in `0..PERMISSION_COUNT`. Handy for per permission data held in fixed
+            /// size arrays. The bit assigned by `bit` is `1 << index`. Variants disabled by
+            /// `#[cfg(...)]` dont take an index, thus the indices of later variants depend on the
+            /// enabled features.
+            pub const fn index(&self) -> usize {
+                match self {
+                    #(#pats => #indices,)*
+                }
+            }
+
+            /// Returns the bit assigned to this variant. This is the `const` equivalent of
+            /// `PermissionBits::to_bits`, useful for building role masks for `ConstCheck`.
+            pub const fn bit(&self) -> u128 {
+                match self {
+                    #(#pats => #bits,)*
+                }
+            }
+
+            /// Returns the name of the permission variant, as set by `#[perm(rename = "...")]` if given.
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    #(#pats => #names,)*
+                }
+            }
+        }
+
+        impl #impl_generics #enum_ty #static_where {
+            /// Every permission variant in declaration order.
+            pub const ALL: &'static [Self] = &[#(#variants),*];
+        }
+
+        impl #impl_generics core::fmt::Display for #enum_ty #where_clause {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl #impl_generics core::str::FromStr for #enum_ty #where_clause {
+            type Err = type_permissions::ParsePermissionError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    #(#names #(| #aliases)* => Ok(#variants),)*
+                    _ => Err(type_permissions::ParsePermissionError::new(s)),
+                }
+            }
+        }
+    }
+}
+
+/// Builds the `registry` function listing the metadata of every permission.
+fn build_registry(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+    variant_options: &[VariantOptions],
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let idents = fields.iter().map(|f| f.ident.to_string());
+    let names = variant_options.iter().map(|x| &x.name);
+    let docs = variant_options.iter().map(|x| &x.doc);
+
+    quote! {
+        impl #impl_generics #enum_ty #where_clause {
+            /// Returns the metadata of every permission in declaration order.
+            pub fn registry() -> &'static [type_permissions::PermissionMeta] {
+                const REGISTRY: &[type_permissions::PermissionMeta] = &[
+                    #(type_permissions::PermissionMeta::new(#idents, #names, #docs),)*
+                ];
+                REGISTRY
+            }
+        }
+    }
+}
+
+/// Builds the `iter` function yielding every variant in declaration order. Unlike `ALL` it builds
+/// the variants by value, thus the enum doesnt need to be `Clone`.
+fn build_iter(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+) -> proc_macro2::TokenStream {
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let variants = fields.iter().map(|f| target.ctor(f));
+    let indices = 0..fields.len();
+
+    quote! {
+        impl #impl_generics #enum_ty #where_clause {
+            /// Returns an iterator over every permission variant in declaration order.
+            pub fn iter() -> impl Iterator<Item = Self> {
+                (0..Self::PERMISSION_COUNT).map(|x| match x {
+                    #(#indices => #variants,)*
+                    _ => unreachable!("the index is always below `PERMISSION_COUNT`"),
+                })
+            }
+        }
+    }
+}
+
+/// Returns the `u64` bit of every variant for `#[permissions(bits)]`. An explicit discriminant,
+/// which has to be a power of two, is taken as the bit of its variant so that the masks match an
+/// existing encoding. Other variants keep the bit they are assigned by `bit`.
+fn parse_bits64(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> syn::Result<Vec<u64>> {
+    let mut bits = Vec::<u64>::with_capacity(fields.len());
+
+    for (idx, f) in fields.iter().enumerate() {
+        let bit = match &f.discriminant {
+            Some((
+                _,
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(x),
+                    ..
+                }),
+            )) => {
+                let bit = x.base10_parse::<u64>()?;
+                if !bit.is_power_of_two() {
+                    return Err(syn::Error::new_spanned(
+                        x,
+                        "discriminants of `bits` permissions must be powers of two",
+                    ));
+                }
+                bit
+            }
+            Some((_, x)) => {
+                return Err(syn::Error::new_spanned(
+                    x,
+                    "discriminants of `bits` permissions must be integer literals",
+                ))
+            }
+            None => 1 << idx,
+        };
+
+        if bits.contains(&bit) {
+            return Err(syn::Error::new_spanned(
+                f,
+                format!("bit `{:#x}` is assigned to more than one permission", bit),
+            ));
+        }
+        bits.push(bit);
+    }
+
+    Ok(bits)
+}
+
+/// Builds the `u64` bitmask conversions enabled by `#[permissions(bits)]`, see `parse_bits64` for
+/// how bits are assigned.
+fn build_bits64(
+    fields: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    target: &Target,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let set_where = target.where_with(quote! { core::hash::Hash + core::cmp::Eq });
+    let variants = fields.iter().map(|f| target.ctor(f)).collect::<Vec<_>>();
+    let pats = fields.iter().map(|f| target.pat(f));
+    let bits = parse_bits64(fields)?;
+
+    Ok(quote! {
+        impl #impl_generics #enum_ty #where_clause {
+            /// Returns the bit assigned to this variant as a `u64`, for storing role sets as plain
+            /// integer bitmasks.
+            pub const fn bit_u64(&self) -> u64 {
+                match self {
+                    #(#pats => #bits,)*
+                }
+            }
+        }
+
+        impl #impl_generics #enum_ty #set_where {
+            /// Folds a set of roles into a `u64` bitmask, the inverse of `from_bits`.
+            pub fn set_to_bits_u64(ops: &type_permissions::HashSet<Self>) -> u64 {
+                ops.iter().fold(0, |acc, x| acc | x.bit_u64())
+            }
+
+            /// Builds the set of roles whose bits are set in `mask`. Bits which arent assigned to
+            /// any variant are ignored.
+            pub fn from_bits(mask: u64) -> type_permissions::HashSet<Self> {
+                let mut ops = type_permissions::HashSet::new();
+                #(
+                    if mask & #bits != 0 {
+                        ops.insert(#variants);
+                    }
+                )*
+                ops
+            }
+        }
+    })
+}
+
+/// Builds `Serialize` and `Deserialize` impls which map each variant to and from its name, see
+/// `VariantOptions::name`.
+#[cfg(feature = "serde")]
+fn build_serde(target: &Target, names: &[String]) -> proc_macro2::TokenStream {
+    let (impl_generics, _, where_clause) = target.generics.split_for_impl();
+    let enum_ty = target.ty();
+    let mut de_generics = target.generics.clone();
+    de_generics.params.insert(0, syn::parse_quote!('de));
+    let (de_impl_generics, _, _) = de_generics.split_for_impl();
+
+    quote! {
+        const _: () = {
+            use type_permissions::__private::serde;
+
+            impl #impl_generics serde::Serialize for #enum_ty #where_clause {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.serialize_str(self.as_str())
+                }
+            }
+
+            impl #de_impl_generics serde::Deserialize<'de> for #enum_ty #where_clause {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    struct Visitor<T>(core::marker::PhantomData<fn() -> T>);
+
+                    impl<'de, T: core::str::FromStr> serde::de::Visitor<'de> for Visitor<T> {
+                        type Value = T;
+
+                        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                            f.write_str("a permission name")
+                        }
+
+                        fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                            value
+                                .parse()
+                                .map_err(|_| E::unknown_variant(value, &[#(#names),*]))
+                        }
+                    }
+
+                    deserializer.deserialize_str(Visitor(core::marker::PhantomData))
+                }
+            }
+        };
+    }
+}
+
+#[cfg(not(feature = "serde"))]
+fn build_serde(_: &Target, _: &[String]) -> proc_macro2::TokenStream {
+    proc_macro2::TokenStream::new()
+}
+
+/// Declares a permission enum and derives `Permissions` on it from a compact syntax, for example
+/// `permissions! { Read, Write implies Read, Admin implies Write }`. A variant can imply several
+/// others with `implies A + B` and join groups with `in billing + support`. The enum is a private
+/// `enum Permissions` unless a header like `pub enum Scopes { ... }` is given, which can carry
+/// attributes such as `#[permissions(prefix = "...")]` as well.
+#[proc_macro]
+pub fn permissions(input: TokenStream) -> TokenStream {
+    TokenStream::from(parse_macro_input!(input as dsl::Dsl).expand())
+}
+
+/// Implementation detail of `#[requires(enum = ...)]`, see the `validate` module.
+#[doc(hidden)]
+#[proc_macro]
+pub fn validate_permissions(input: TokenStream) -> TokenStream {
+    TokenStream::from(parse_macro_input!(input as validate::Validate).expand())
+}
+
+/// Attribute macro which type constricts a function to only be callable with a `PhantomToken`
+/// carrying the listed permissions. The function gets a new first argument (after `self` for
+/// methods) of type `PhantomToken<T>` where `T` is bound on the permission traits. The rest of
+/// the signature, including `async`, existing generics and where clauses, is left untouched.
+///
+/// Multiple permissions get folded into a right-nested `And` chain, for example
+/// `#[requires("A", "B", "C")]` requires a `PhantomToken<And<Perms, A, And<Perms, B, C>>>`. The
+/// bound is generic over the operand order, thus a token carrying
+/// `And<Perms, And<Perms, B, C>, A>` is accepted as well.
+///
+/// Functions which already have the role set at hand, for example methods on a service carrying
+/// the roles of the caller, can instead check it at runtime with
+/// `#[requires(from = "self.ctx.roles", perm = "CanWrite")]`. No token parameter is added, rather
+/// the function starts with a guard returning the `PermissionError` through `?` if the role set
+/// doesnt satisfy the permissions, thus the function has to return a `Result` whose error type
+/// converts from it. `perm` can be repeated to combine several permissions.
+///
+/// A misspelled permission otherwise only surfaces as a missing trait. Passing the enum first, as
+/// in `#[requires(enum = Permissions, "CanRead")]`, checks the names against the markers
+/// generated for it instead and suggests the closest one on a typo.
+#[proc_macro_attribute]
+pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_requires(attr, item, Combinator::And)
+}
+
+/// Same as `requires`, spelling out that every listed permission is required.
+#[proc_macro_attribute]
+pub fn requires_all(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_requires(attr, item, Combinator::And)
+}
+
+/// Like `requires` except that any one of the listed permissions suffices. Multiple permissions
+/// get folded into a right-nested `Or` chain, for example `#[requires_any("A", "B")]` requires a
+/// `PhantomToken<Or<Perms, A, B>>`.
+#[proc_macro_attribute]
+pub fn requires_any(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand_requires(attr, item, Combinator::Or)
+}
+
+/// Combinator multiple permissions passed to the `requires` family get folded with.
+#[derive(Clone, Copy)]
+enum Combinator {
+    And,
+    Or,
+}
+
+impl Combinator {
+    /// Returns the paths to the combinator struct and its trait.
+    fn paths(self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        match self {
+            Self::And => (
+                quote! { type_permissions::And },
+                quote! { type_permissions::TAnd },
+            ),
+            Self::Or => (
+                quote! { type_permissions::Or },
+                quote! { type_permissions::TOr },
+            ),
+        }
+    }
+}
+
+/// Arguments of the `requires` family, an optional `enum = Path` naming the permission enum to
+/// validate the permission names against, followed by the permissions.
+struct RequiresArgs {
+    enum_path: Option<syn::Path>,
+    args: syn::AttributeArgs,
+}
+
+impl syn::parse::Parse for RequiresArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut enum_path = None;
+        if input.peek(syn::Token![enum]) {
+            input.parse::<syn::Token![enum]>()?;
+            input.parse::<syn::Token![=]>()?;
+            enum_path = Some(input.parse()?);
+            if !input.is_empty() {
+                input.parse::<syn::Token![,]>()?;
+            }
+        }
+
+        let args = syn::punctuated::Punctuated::<_, syn::Token![,]>::parse_terminated(input)?;
+        Ok(Self {
+            enum_path,
+            args: args.into_iter().collect(),
+        })
+    }
+}
+
+fn expand_requires(attr: TokenStream, item: TokenStream, combinator: Combinator) -> TokenStream {
+    let args = parse_macro_input!(attr as RequiresArgs);
+    let mut function = parse_macro_input!(item as syn::ItemFn);
+
+    if let Err(e) = apply_requires(&args, &mut function.sig, &mut function.block, combinator) {
+        return e.to_compile_error().into();
+    }
+
+    TokenStream::from(quote! { #function })
+}
+
+/// Applies the `requires` arguments `args` to the function with the signature `sig` and body
+/// `block`, either adding the token parameter or the guard.
+fn apply_requires(
+    args: &RequiresArgs,
+    sig: &mut syn::Signature,
+    block: &mut syn::Block,
+    combinator: Combinator,
+) -> syn::Result<()> {
+    let RequiresArgs { enum_path, args } = args;
+
+    // Inserted last for it to end up at the very top of the body, ahead of the guard.
+    let validation = enum_path.as_ref().map(|path| -> syn::Stmt {
+        let markers = validate::markers_macro_path(path);
+        let perms = args.iter().filter_map(|x| match x {
+            syn::NestedMeta::Lit(syn::Lit::Str(x)) => Some(x),
+            syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("perm") => {
+                match &x.lit {
+                    syn::Lit::Str(x) => Some(x),
+                    _ => None,
+                }
+            }
+            _ => None,
+        });
+        syn::parse_quote!(#markers! { #(#perms),* })
+    });
+
+    if is_guard(args) {
+        let (from, perms) = parse_guard(args)?;
+        add_guard(block, &from, &perms, combinator);
+    } else {
+        let perms = parse_permission_list(args)?;
+        add_token_param(sig, &perms, combinator);
+    }
+    block.stmts.splice(0..0, validation);
+
+    Ok(())
+}
+
+/// Whether `args` use the guard mode of `requires`, that is `from = "..."` and `perm = "..."`.
+fn is_guard(args: &[syn::NestedMeta]) -> bool {
+    args.iter()
+        .any(|x| matches!(x, syn::NestedMeta::Meta(syn::Meta::NameValue(_))))
+}
+
+/// Applies `requires` to every method of an `impl` block, for services where every method needs
+/// the same base permission. Takes the same arguments as `requires`, thus each method either gets
+/// a token parameter, after `self` if it has one, or a guard at the top of its body. Methods can
+/// still carry their own `requires` on top of it, whose token then comes ahead of the one added by
+/// `requires_impl`.
+///
+/// Trait impls cant change the signatures of their methods, thus only the guard mode is accepted
+/// on them.
+#[proc_macro_attribute]
+pub fn requires_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as RequiresArgs);
+    let mut block = parse_macro_input!(item as syn::ItemImpl);
+
+    if let (Some((_, path, _)), false) = (&block.trait_, is_guard(&args.args)) {
+        return syn::Error::new_spanned(
+            path,
+            "`requires_impl` cant add token parameters to the methods of a trait impl, use \
+             `from = \"...\"` and `perm = \"...\"` to check a role set instead",
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    for item in &mut block.items {
+        if let syn::ImplItem::Method(method) = item {
+            if let Err(e) =
+                apply_requires(&args, &mut method.sig, &mut method.block, Combinator::And)
+            {
+                return e.to_compile_error().into();
+            }
+        }
+    }
+
+    TokenStream::from(quote! { #block })
+}
+
+/// Parses the string literal arguments of `requires` into paths to the permission structs.
+fn parse_permission_list(args: &[syn::NestedMeta]) -> syn::Result<Vec<syn::Path>> {
+    if args.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected at least one permission, for example `#[requires(\"CanRead\")]`",
+        ));
+    }
+
+    args.iter()
+        .map(|arg| match arg {
+            syn::NestedMeta::Lit(syn::Lit::Str(x)) => x.parse::<syn::Path>(),
+            x => Err(syn::Error::new_spanned(
+                x,
+                "expected the permission name as a string literal",
+            )),
+        })
+        .collect()
+}
+
+/// Parses the `from = "..."` and `perm = "..."` arguments of the guard mode of `requires` into
+/// the role set expression and the paths to the permission structs.
+fn parse_guard(args: &[syn::NestedMeta]) -> syn::Result<(syn::Expr, Vec<syn::Path>)> {
+    let mut from = None;
+    let mut perms = Vec::new();
+
+    for arg in args {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("from") => {
+                from = Some(lit_str(&x.lit)?.parse::<syn::Expr>()?);
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(x)) if x.path.is_ident("perm") => {
+                perms.push(lit_str(&x.lit)?.parse::<syn::Path>()?);
+            }
+            x => {
+                return Err(syn::Error::new_spanned(
+                    x,
+                    "expected `from = \"...\"` or `perm = \"...\"`",
+                ))
+            }
+        }
+    }
+
+    let from = from.ok_or_else(|| {
+        syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected the role set to check, for example `from = \"self.roles\"`",
+        )
+    })?;
+
+    if perms.is_empty() {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "expected at least one permission, for example `perm = \"CanRead\"`",
+        ));
+    }
+
+    Ok((from, perms))
+}
+
+/// Inserts a guard at the top of `block` which returns early with the `PermissionError` if the
+/// role set `from` doesnt satisfy `perms`.
+fn add_guard(
+    block: &mut syn::Block,
+    from: &syn::Expr,
+    perms: &[syn::Path],
+    combinator: Combinator,
+) {
+    let (combinator, _) = combinator.paths();
+    let perm = perms
+        .iter()
+        .rev()
+        .map(|x| quote! { #x })
+        .reduce(|acc, x| quote! { #combinator<_, #x, #acc> })
+        .expect("perms is never empty");
+
+    block.stmts.insert(
+        0,
+        syn::parse_quote!(<#perm as type_permissions::Dispatch<_>>::require(&#from)?;),
+    );
+}
+
+/// Returns the path to the trait generated for the permission struct at `path`.
+fn permission_trait(path: &syn::Path) -> syn::Path {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = syn::Ident::new(&format!("T{}", last.ident), last.ident.span());
+    }
+    path
+}
+
+/// Returns a type parameter name starting with `base` which `sig` doesnt declare yet, such that
+/// stacking `requires` on top of `requires_impl` adds a second token instead of clashing.
+fn fresh_type_param(sig: &syn::Signature, base: &str) -> syn::Ident {
+    let taken = |name: &str| sig.generics.type_params().any(|x| x.ident == name);
+    let name = Some(base.to_string())
+        .into_iter()
+        .chain((1..).map(|x| format!("{}{}", base, x)))
+        .find(|x| !taken(x))
+        .expect("there are infinitely many candidates");
+
+    syn::Ident::new(&name, proc_macro2::Span::call_site())
+}
+
+/// Injects the `PhantomToken` parameter into `sig` alongside the generics and bounds it needs.
+fn add_token_param(sig: &mut syn::Signature, perms: &[syn::Path], combinator: Combinator) {
+    let token_ty = fresh_type_param(sig, "__RequiresToken");
+    let perm_ty = fresh_type_param(sig, "__RequiresPerm");
+    let order_ty = fresh_type_param(sig, "__RequiresOrder");
+
+    if let [perm] = perms {
+        let bound = permission_trait(perm);
+        sig.generics
+            .params
+            .push(syn::parse_quote!(#token_ty: ?Sized + #bound));
+    } else {
+        let (combinator, combinator_trait) = combinator.paths();
+        let (first, rest) = perms.split_first().expect("perms is never empty");
+        let rest = rest
+            .iter()
+            .rev()
+            .map(|x| quote! { #x })
+            .reduce(|acc, x| quote! { #combinator<#perm_ty, #x, #acc> })
+            .expect("multiple permissions were given");
+
+        sig.generics.params.push(syn::parse_quote!(#perm_ty));
+        sig.generics.params.push(syn::parse_quote!(#order_ty));
+        sig.generics.params.push(syn::parse_quote!(
+            #token_ty: ?Sized + #combinator_trait<#perm_ty, #first, #rest, #order_ty>
+        ));
+
+        let where_clause = sig.generics.make_where_clause();
+        where_clause.predicates.push(syn::parse_quote!(
+            #perm_ty: Sized + core::hash::Hash + core::cmp::Eq + Clone
+        ));
+        for perm in perms {
+            where_clause
+                .predicates
+                .push(syn::parse_quote!(#perm: type_permissions::Dispatch<#perm_ty>));
+        }
+    }
+
+    let receiver = matches!(sig.inputs.first(), Some(syn::FnArg::Receiver(_)));
+    sig.inputs.insert(
+        receiver as usize,
+        syn::parse_quote!(_: type_permissions::PhantomToken<#token_ty>),
+    );
+}
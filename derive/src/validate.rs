@@ -0,0 +1,121 @@
+//! Validation of the permission names passed to `#[requires(enum = ...)]`. An attribute macro
+//! cant look at other items, thus the derive exports a hidden `macro_rules!` next to the enum
+//! which knows the names of every marker. `requires` invokes it with the requested names, which
+//! forwards both lists to `validate_permissions!` to compare them.
+use quote::quote;
+use syn::parse::Parse;
+use syn::parse::ParseStream;
+use syn::punctuated::Punctuated;
+use syn::Token;
+
+use crate::Target;
+
+/// Returns the name of the hidden macro listing the markers of the enum `ident`.
+fn markers_macro_name(ident: &syn::Ident) -> syn::Ident {
+    syn::Ident::new(&format!("__typed_perm_markers_{}", ident), ident.span())
+}
+
+/// Returns the path to the hidden macro listing the markers of the enum at `path`.
+pub(crate) fn markers_macro_path(path: &syn::Path) -> syn::Path {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = markers_macro_name(&last.ident);
+        last.arguments = syn::PathArguments::None;
+    }
+    path
+}
+
+/// Builds the hidden macro listing `markers`, the names of every type usable in `requires`.
+pub(crate) fn build_markers_macro(
+    target: &Target,
+    markers: &[syn::Ident],
+) -> proc_macro2::TokenStream {
+    let name = markers_macro_name(target.ident);
+
+    quote! {
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #name {
+            ($($perms:tt)*) => {
+                type_permissions::__private::validate_permissions! { [#(#markers),*] $($perms)* }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #name;
+    }
+}
+
+/// Input of `validate_permissions!`, the known marker names in brackets followed by the
+/// requested names as passed to `requires`.
+pub(crate) struct Validate {
+    markers: Vec<syn::Ident>,
+    perms: Punctuated<syn::LitStr, Token![,]>,
+}
+
+impl Parse for Validate {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::bracketed!(content in input);
+        let markers = Punctuated::<syn::Ident, Token![,]>::parse_terminated(&content)?;
+
+        Ok(Self {
+            markers: markers.into_iter().collect(),
+            perms: Punctuated::parse_terminated(input)?,
+        })
+    }
+}
+
+impl Validate {
+    /// Emits an error for every requested name which isnt a known marker, suggesting the closest
+    /// known one if any is similar enough.
+    pub(crate) fn expand(&self) -> proc_macro2::TokenStream {
+        let errors = self.perms.iter().filter_map(|perm| {
+            let path = perm.parse::<syn::Path>().ok()?;
+            let name = path.segments.last()?.ident.to_string();
+            if self.markers.iter().any(|x| *x == name) {
+                return None;
+            }
+
+            let message = match self.suggestion(&name) {
+                Some(x) => format!("unknown permission `{}`, did you mean `{}`?", name, x),
+                None => format!("unknown permission `{}`", name),
+            };
+            Some(syn::Error::new_spanned(perm, message).to_compile_error())
+        });
+
+        quote! { #(#errors)* }
+    }
+
+    /// Returns the known marker closest to `name`, if it is within a third of its length.
+    fn suggestion(&self, name: &str) -> Option<&syn::Ident> {
+        let threshold = (name.len() / 3).max(1);
+
+        self.markers
+            .iter()
+            .map(|x| (distance(name, &x.to_string()), x))
+            .filter(|(distance, _)| *distance <= threshold)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, x)| x)
+    }
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, x) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+
+        for (j, y) in b.iter().enumerate() {
+            let substitution = diagonal + (x != *y) as usize;
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+
+    row[b.len()]
+}
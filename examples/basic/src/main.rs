@@ -1,7 +1,6 @@
-use type_permissions::And;
 use type_permissions::Dispatch;
 use type_permissions::PhantomToken;
-use type_permissions::*;
+use typed_perm_derive::requires;
 use typed_perm_derive::Permissions;
 
 #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
@@ -14,10 +13,17 @@ fn fun_y<T: ?Sized + TCanCallFunctionY>(_: PhantomToken<T>) {
     println!("Hello world");
 }
 
+#[requires("CanCallFunctionX")]
+fn fun_x() {
+    println!("Hello from x");
+}
+
 fn get_typed_perm<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
     unsafe { PhantomToken::new_unchecked() }
 }
 
 fn main() {
     println!("{:?}", CanCallFunctionX::dispatch());
+    fun_x(get_typed_perm::<CanCallFunctionX>());
+    fun_y(get_typed_perm::<CanCallFunctionY>());
 }
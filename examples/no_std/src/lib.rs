@@ -0,0 +1,48 @@
+//! Builds the derive and the combinators without `std`, this crate only exists to make sure the
+//! `no_std` feature keeps compiling.
+#![no_std]
+
+use type_permissions::requires;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+pub enum Permissions {
+    #[implies(CanUnlock)]
+    CanConfigure,
+    CanUnlock,
+}
+
+#[requires("CanUnlock")]
+pub fn unlock() {}
+
+#[requires("CanConfigure", "CanUnlock")]
+pub fn configure() {}
+
+/// Unlocks the device if the role set allows it, returns whether it did.
+pub fn try_unlock(roles: &HashSet<Permissions>) -> bool {
+    match CanUnlock::try_into_token(roles) {
+        Some(token) => {
+            unlock(token);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Configures the device if the role set allows it, returns whether it did.
+pub fn try_configure(roles: &HashSet<Permissions>) -> bool {
+    let token: Option<PhantomToken<And<Permissions, CanConfigure, CanUnlock>>> =
+        Dispatch::try_into_token(roles);
+
+    match token {
+        Some(token) => {
+            configure(token);
+            true
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,110 @@
+//! Counts the allocations made by `dispatch` for a left-nested chain of 16 permissions, comparing
+//! the single set filled through `collect_into` against unioning a fresh set at every node.
+//! Run with `cargo bench --bench allocations`.
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::hint::black_box;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::Permissions;
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    P0,
+    P1,
+    P2,
+    P3,
+    P4,
+    P5,
+    P6,
+    P7,
+    P8,
+    P9,
+    P10,
+    P11,
+    P12,
+    P13,
+    P14,
+    P15,
+}
+
+/// Builds a left-nested `And` chain, the worst case for set unioning.
+macro_rules! left_chain {
+    ($t:ty) => { $t };
+    ($t:ty, $u:ty $(, $rest:ty)*) => { left_chain!(And<Permissions, $t, $u> $(, $rest)*) };
+}
+
+type Chain = left_chain!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15);
+
+/// The previous `And::dispatch`, which unions the sets of both operands at every node.
+trait UnionDispatch {
+    fn union_dispatch() -> HashSet<Permissions>;
+}
+
+impl<T: UnionDispatch + Dispatch<Permissions>, U: UnionDispatch + Dispatch<Permissions>>
+    UnionDispatch for And<Permissions, T, U>
+{
+    fn union_dispatch() -> HashSet<Permissions> {
+        T::union_dispatch()
+            .union(&U::union_dispatch())
+            .cloned()
+            .collect()
+    }
+}
+
+macro_rules! leaf_union_dispatch {
+    ($($t:ty),*) => {
+        $(impl UnionDispatch for $t {
+            fn union_dispatch() -> HashSet<Permissions> {
+                <$t>::dispatch()
+            }
+        })*
+    };
+}
+
+leaf_union_dispatch!(P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15);
+
+const ITERATIONS: usize = 1_000;
+
+fn bench(name: &str, f: impl Fn() -> HashSet<Permissions>) {
+    assert_eq!(f().len(), 16);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let allocations = ALLOCATIONS.load(Ordering::SeqCst) - before;
+    println!(
+        "{:<24} {:>6.1} allocations/iter",
+        name,
+        allocations as f64 / ITERATIONS as f64
+    );
+}
+
+fn main() {
+    bench("dispatch (collect_into)", Chain::dispatch);
+    bench("dispatch (union)", Chain::union_dispatch);
+}
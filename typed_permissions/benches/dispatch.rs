@@ -0,0 +1,65 @@
+//! Compares the `HashSet` superset check against the cached and bitset backends for a chain of
+//! permissions.
+//! Run with `cargo bench`.
+use std::hint::black_box;
+use std::time::Instant;
+
+use type_permissions::And;
+#[cfg(not(feature = "no_std"))]
+use type_permissions::CachedDispatch;
+use type_permissions::Dispatch;
+use type_permissions::DispatchBits;
+use type_permissions::HashSet;
+use type_permissions::PermissionBits;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    P0,
+    P1,
+    P2,
+    P3,
+    P4,
+    P5,
+    P6,
+    P7,
+}
+
+type Chain = And<
+    Permissions,
+    P0,
+    And<
+        Permissions,
+        P1,
+        And<Permissions, P2, And<Permissions, P3, And<Permissions, P4, And<Permissions, P5, P6>>>>,
+    >,
+>;
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench(name: &str, f: impl Fn() -> bool) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(f());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<24} {:>10.1} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / f64::from(ITERATIONS)
+    );
+}
+
+fn main() {
+    let roles: HashSet<_> = Permissions::ALL.iter().cloned().collect();
+    let mask = Permissions::set_to_bits(&roles);
+
+    bench("check_match (HashSet)", || {
+        Chain::check_match(black_box(&roles))
+    });
+    #[cfg(not(feature = "no_std"))]
+    bench("check_match_cached", || {
+        Chain::check_match_cached(black_box(&roles))
+    });
+    bench("check_bits (u128)", || Chain::check_bits(black_box(mask)));
+}
@@ -0,0 +1,86 @@
+//! [actix-web](https://docs.rs/actix-web) integration. Handlers can declare the permissions they
+//! require through the [`TypedPerm`] extractor, which derives a `PhantomToken` from the role set
+//! your auth middleware stored in the request extensions.
+//!
+//! # Example
+//! ```no_run
+//! use type_permissions::actix::TypedPerm;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) {}
+//!
+//! // Requests reaching this handler without `CanDeleteUsers` in their
+//! // `HashSet<Permissions>` extension are rejected with `403 Forbidden`.
+//! async fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) -> &'static str {
+//!     delete_users(perm.into_inner());
+//!     "deleted"
+//! }
+//! ```
+use std::future::ready;
+use std::future::Ready;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use actix_web::dev::Payload;
+use actix_web::error::ErrorForbidden;
+use actix_web::Error;
+use actix_web::FromRequest;
+use actix_web::HttpMessage;
+use actix_web::HttpRequest;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Extractor which derives a `PhantomToken<T>` from the `HashSet<Z>` of roles stored in the
+/// request extensions. Requests are rejected with `403 Forbidden` if the extension is missing or
+/// the roles dont satisfy `T`.
+pub struct TypedPerm<Z, T: ?Sized> {
+    token: PhantomToken<T>,
+    _z: PhantomData<fn() -> Z>,
+}
+
+impl<Z, T: ?Sized> TypedPerm<Z, T> {
+    /// Returns the derived `PhantomToken`.
+    pub fn into_inner(self) -> PhantomToken<T> {
+        self.token
+    }
+}
+
+impl<Z, T: ?Sized> Deref for TypedPerm<Z, T> {
+    type Target = PhantomToken<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.token
+    }
+}
+
+impl<Z, T> FromRequest for TypedPerm<Z, T>
+where
+    Z: Sized + Hash + Eq + 'static,
+    T: ?Sized + Dispatch<Z>,
+{
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let token = req
+            .extensions()
+            .get::<HashSet<Z>>()
+            .and_then(T::try_into_token)
+            .map(|token| TypedPerm {
+                token,
+                _z: PhantomData,
+            })
+            .ok_or_else(|| ErrorForbidden("missing required permissions"));
+
+        ready(token)
+    }
+}
@@ -0,0 +1,67 @@
+//! [axum](https://docs.rs/axum) integration. Endpoints can declare the permissions they require
+//! purely through the [`TypedPerm`] extractor, which derives a `PhantomToken` from the role set
+//! your auth middleware stored in the request extensions.
+//!
+//! # Example
+//! ```no_run
+//! use type_permissions::axum::TypedPerm;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) {}
+//!
+//! // Requests reaching this handler without `CanDeleteUsers` in their
+//! // `HashSet<Permissions>` extension are rejected with `401 Unauthorized`.
+//! async fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) {
+//!     delete_users(perm.0);
+//! }
+//! ```
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Extractor which derives a `PhantomToken<T>` from the `HashSet<Z>` of roles stored in the
+/// request extensions. Requests are rejected with `401 Unauthorized` if the extension is missing
+/// or the roles dont satisfy `T`.
+///
+/// The token is the public first field, handlers can take it out with `perm.0` or
+/// [`TypedPerm::into_inner`]. `Z` names the permission enum whose role set is looked up, a permission type can dispatch over
+/// several enums, thus the `FromRequestParts` impl couldnt name it otherwise.
+pub struct TypedPerm<Z, T: ?Sized>(pub PhantomToken<T>, PhantomData<fn() -> Z>);
+
+impl<Z, T: ?Sized> TypedPerm<Z, T> {
+    /// Returns the derived `PhantomToken`.
+    pub fn into_inner(self) -> PhantomToken<T> {
+        self.0
+    }
+}
+
+impl<S, Z, T> FromRequestParts<S> for TypedPerm<Z, T>
+where
+    S: Send + Sync,
+    Z: Sized + Hash + Eq + Send + Sync + 'static,
+    T: ?Sized + Dispatch<Z>,
+{
+    type Rejection = StatusCode;
+
+    async fn from_request_parts(parts: &mut Parts, _: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<HashSet<Z>>()
+            .and_then(T::try_into_token)
+            .map(|token| TypedPerm(token, PhantomData))
+            .ok_or(StatusCode::UNAUTHORIZED)
+    }
+}
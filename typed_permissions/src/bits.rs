@@ -0,0 +1,205 @@
+//! Bitset backed permission checking. Every variant of an enum deriving `Permissions` is assigned
+//! a bit in a `u128`, so role checks become a couple of bitwise ops instead of building and
+//! comparing `HashSet`s.
+//!
+//! # Compile time checks
+//! When the role set is known at compile time, for example for internal tooling running under a
+//! fixed service identity, the check can be evaluated in a `const` context through
+//! [`ConstCheck`] and [`const_check`]:
+//! ```
+//! use type_permissions::const_check;
+//! use type_permissions::And;
+//! use type_permissions::Permissions;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanRead,
+//!     CanWrite,
+//!     CanDelete,
+//! }
+//!
+//! const ROLES: u128 = Permissions::CanRead.bit() | Permissions::CanWrite.bit();
+//!
+//! const _: () = assert!(const_check::<And<Permissions, CanRead, CanWrite>, ROLES>());
+//! const _: () = assert!(!const_check::<CanDelete, ROLES>());
+//! ```
+//!
+//! Trait methods cant be `const fn` on stable rust, thus neither [`DispatchBits::check_bits`]
+//! nor [`PermissionBits::to_bits`] are usable in `const` contexts. [`ConstCheck`] works around
+//! this by evaluating the check in an associated const, which in turn means the role set has to
+//! be passed as a const generic rather than a function argument, and that the `const` item has to
+//! name the concrete permission type. The derive additionally generates an inherent
+//! `const fn bit(&self) -> u128` on the enum to build the role mask with.
+//!
+//! # Stored bitmasks
+//! Enums with at most 64 variants can be annotated with `#[permissions(bits)]` to additionally
+//! generate `const fn bit_u64(&self) -> u64`, `set_to_bits_u64` and `from_bits(mask: u64)`, which
+//! convert role sets to and from bitmasks stored in plain integer columns. Variants are assigned
+//! the same bits as by `bit`, unless they have an explicit discriminant, which then has to be a
+//! power of two and is used as the bit instead. This way the masks can match an existing
+//! encoding, for example `Read = 1, Write = 2, Delete = 8`.
+use core::hash::Hash;
+
+use crate::And;
+use crate::Dispatch;
+use crate::HashSet;
+use crate::Not;
+use crate::Nothing;
+use crate::Or;
+use crate::PhantomToken;
+use crate::Xor;
+
+/// This trait is auto implemented by the `Permissions` derive for the enum itself. It maps each
+/// variant to the bit used by [`DispatchBits`].
+pub trait PermissionBits: Sized + Hash + Eq {
+    /// Returns the bit assigned to this variant.
+    fn to_bits(&self) -> u128;
+
+    /// Folds a set of roles into a single bitmask.
+    fn set_to_bits(ops: &HashSet<Self>) -> u128 {
+        ops.iter().fold(0, |acc, x| acc | x.to_bits())
+    }
+}
+
+/// Bitset equivalent of [`Dispatch`]. It is implemented for every generated permission struct as
+/// well as the `And`, `Or`, `Xor` and `Not` combinators.
+pub trait DispatchBits<Z: PermissionBits>: Dispatch<Z> {
+    /// Returns the bitmask of the permissions required by this type.
+    fn bits() -> u128;
+
+    /// Checks whether the `ops` bitmask matches the permissions required by this type.
+    fn check_bits(ops: u128) -> bool {
+        ops & Self::bits() == Self::bits()
+    }
+
+    /// Same as [`Dispatch::try_into_token`] except that the role set is passed as a bitmask.
+    fn try_into_token_bits(ops: u128) -> Option<PhantomToken<Self>> {
+        if Self::check_bits(ops) {
+            Some(unsafe { PhantomToken::new_unchecked() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<Z, T, U> DispatchBits<Z> for And<Z, T, U>
+where
+    Z: PermissionBits + Clone,
+    T: ?Sized + DispatchBits<Z>,
+    U: ?Sized + DispatchBits<Z>,
+{
+    fn bits() -> u128 {
+        T::bits() | U::bits()
+    }
+
+    fn check_bits(ops: u128) -> bool {
+        T::check_bits(ops) && U::check_bits(ops)
+    }
+}
+
+impl<Z, T, U> DispatchBits<Z> for Or<Z, T, U>
+where
+    Z: PermissionBits + Clone,
+    T: ?Sized + DispatchBits<Z>,
+    U: ?Sized + DispatchBits<Z>,
+{
+    fn bits() -> u128 {
+        T::bits() | U::bits()
+    }
+
+    fn check_bits(ops: u128) -> bool {
+        T::check_bits(ops) || U::check_bits(ops)
+    }
+}
+
+impl<Z, T, U> DispatchBits<Z> for Xor<Z, T, U>
+where
+    Z: PermissionBits + Clone,
+    T: ?Sized + DispatchBits<Z>,
+    U: ?Sized + DispatchBits<Z>,
+{
+    fn bits() -> u128 {
+        T::bits() | U::bits()
+    }
+
+    fn check_bits(ops: u128) -> bool {
+        T::check_bits(ops) != U::check_bits(ops)
+    }
+}
+
+impl<Z, T> DispatchBits<Z> for Not<Z, T>
+where
+    Z: PermissionBits + Clone,
+    T: ?Sized + DispatchBits<Z>,
+{
+    fn bits() -> u128 {
+        0
+    }
+
+    fn check_bits(ops: u128) -> bool {
+        !T::check_bits(ops)
+    }
+}
+
+impl<Z> DispatchBits<Z> for Nothing<Z>
+where
+    Z: PermissionBits,
+{
+    fn bits() -> u128 {
+        0
+    }
+}
+
+/// `const` equivalent of [`DispatchBits::check_bits`], `OK` is whether the `ROLES` bitmask
+/// satisfies `Self`. See the [module documentation](self) on why this is a separate trait.
+pub trait ConstCheck<const ROLES: u128> {
+    /// Whether `ROLES` satisfies `Self`.
+    const OK: bool;
+}
+
+/// Evaluates [`ConstCheck::OK`] for `T`, usable in `const` items and `const` assertions.
+pub const fn const_check<T: ?Sized + ConstCheck<ROLES>, const ROLES: u128>() -> bool {
+    T::OK
+}
+
+impl<Z, T, U, const ROLES: u128> ConstCheck<ROLES> for And<Z, T, U>
+where
+    Z: Hash + Eq,
+    T: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+    U: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+{
+    const OK: bool = T::OK && U::OK;
+}
+
+impl<Z, T, U, const ROLES: u128> ConstCheck<ROLES> for Or<Z, T, U>
+where
+    Z: Hash + Eq,
+    T: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+    U: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+{
+    const OK: bool = T::OK || U::OK;
+}
+
+impl<Z, T, U, const ROLES: u128> ConstCheck<ROLES> for Xor<Z, T, U>
+where
+    Z: Hash + Eq,
+    T: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+    U: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+{
+    const OK: bool = T::OK != U::OK;
+}
+
+impl<Z, T, const ROLES: u128> ConstCheck<ROLES> for Not<Z, T>
+where
+    Z: Hash + Eq,
+    T: ?Sized + Dispatch<Z> + ConstCheck<ROLES>,
+{
+    const OK: bool = !T::OK;
+}
+
+impl<Z, const ROLES: u128> ConstCheck<ROLES> for Nothing<Z>
+where
+    Z: Hash + Eq,
+{
+    const OK: bool = true;
+}
@@ -0,0 +1,65 @@
+//! Conversion of tokens between permission enums. Multi-service codebases often end up with an
+//! enum per service, [`PermBridge`] maps equivalent permissions of one enum onto the other so
+//! tokens can cross the boundary.
+use core::hash::Hash;
+
+use crate::Dispatch;
+use crate::Erasable;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Mapping from the permissions of `Self` onto equivalent permissions of `Z`. Implement this on
+/// the source enum, returning `None` for permissions without a counterpart.
+pub trait PermBridge<Z: Hash + Eq>: Sized + Hash + Eq {
+    /// Returns the permission of `Z` equivalent to `self`.
+    fn bridge(&self) -> Option<Z>;
+}
+
+impl<T: ?Sized> PhantomToken<T> {
+    /// Converts this token into a token of the permission type `U` of another enum. The
+    /// permissions proven by this token are mapped through [`PermBridge`] and the token is only
+    /// converted if the mapped set satisfies `U`. Like [`PhantomToken::erase`] this is only
+    /// available for [`Erasable`] types, as the `dispatch` sets of the other types dont list
+    /// permissions that were actually granted.
+    ///
+    /// # Example
+    /// ```
+    /// mod billing {
+    ///     #[derive(type_permissions::Permissions, Hash, Eq, PartialEq, Clone)]
+    ///     pub enum Permissions {
+    ///         Refund,
+    ///     }
+    /// }
+    ///
+    /// mod payments {
+    ///     #[derive(type_permissions::Permissions, Hash, Eq, PartialEq, Clone)]
+    ///     pub enum Permissions {
+    ///         Reverse,
+    ///     }
+    /// }
+    ///
+    /// impl type_permissions::PermBridge<payments::Permissions> for billing::Permissions {
+    ///     fn bridge(&self) -> Option<payments::Permissions> {
+    ///         match self {
+    ///             billing::Permissions::Refund => Some(payments::Permissions::Reverse),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let token = unsafe { type_permissions::PhantomToken::<billing::Refund>::new_unchecked() };
+    /// let _: type_permissions::PhantomToken<payments::Reverse> = token.map().unwrap();
+    /// ```
+    pub fn map<Z, U, Z2>(self) -> Option<PhantomToken<U>>
+    where
+        T: Erasable<Z>,
+        Z: PermBridge<Z2>,
+        U: ?Sized + Dispatch<Z2>,
+        Z2: Sized + Hash + Eq,
+    {
+        let mapped = T::dispatch()
+            .iter()
+            .filter_map(PermBridge::bridge)
+            .collect::<HashSet<_>>();
+        U::try_into_token(&mapped)
+    }
+}
@@ -0,0 +1,89 @@
+//! Construction of role sets from string claims, such as the permissions listed in a JWT.
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::hash::Hash;
+use core::str::FromStr;
+
+use crate::HashSet;
+
+/// Builds a role set out of string claims by parsing each claim with the `FromStr` impl
+/// generated by the `Permissions` derive. Claims which dont name any permission are collected
+/// separately instead of failing the whole build, see [`RoleSetBuilder::unknown`].
+///
+/// # Example
+/// ```
+/// use type_permissions::Dispatch;
+/// use type_permissions::Permissions;
+/// use type_permissions::RoleSetBuilder;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+/// enum Permissions {
+///     Read,
+///     Write,
+/// }
+///
+/// let builder = RoleSetBuilder::<Permissions>::from_space_separated("Read Write Fly");
+/// assert_eq!(builder.unknown(), &["Fly".to_string()]);
+///
+/// let roles = builder.build();
+/// assert!(Write::try_into_token(&roles).is_some());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RoleSetBuilder<Z: Hash + Eq> {
+    roles: HashSet<Z>,
+    unknown: Vec<String>,
+}
+
+impl<Z: Hash + Eq + FromStr> RoleSetBuilder<Z> {
+    /// Creates a builder without any claims.
+    pub fn new() -> Self {
+        Self {
+            roles: HashSet::new(),
+            unknown: Vec::new(),
+        }
+    }
+
+    /// Creates a builder out of a list of claims.
+    pub fn from_claims<'a>(claims: impl IntoIterator<Item = &'a str>) -> Self {
+        Self::new().claims(claims)
+    }
+
+    /// Creates a builder out of a whitespace separated list of claims, matching the format of the
+    /// OAuth `scope` parameter.
+    pub fn from_space_separated(scope: &str) -> Self {
+        Self::from_claims(scope.split_whitespace())
+    }
+
+    /// Adds a single claim.
+    pub fn claim(mut self, claim: &str) -> Self {
+        match claim.parse() {
+            Ok(x) => {
+                self.roles.insert(x);
+            }
+            Err(_) => self.unknown.push(claim.to_string()),
+        }
+        self
+    }
+
+    /// Adds a list of claims.
+    pub fn claims<'a>(self, claims: impl IntoIterator<Item = &'a str>) -> Self {
+        claims.into_iter().fold(self, Self::claim)
+    }
+
+    /// Returns the claims which didnt parse into a permission, in the order they were added.
+    pub fn unknown(&self) -> &[String] {
+        &self.unknown
+    }
+
+    /// Returns the role set built out of every claim that parsed.
+    pub fn build(self) -> HashSet<Z> {
+        self.roles
+    }
+}
+
+impl<Z: Hash + Eq + FromStr> Default for RoleSetBuilder<Z> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
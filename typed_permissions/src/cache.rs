@@ -0,0 +1,121 @@
+//! Memoized dispatch sets. `Dispatch::dispatch` rebuilds its set on every call, which for deep
+//! `And`/`Or` trees means allocating and unioning several `HashSet`s for every single check.
+//! [`CachedDispatch`] computes the set once per type and hands out a `&'static` reference to it
+//! from then on, so repeated token derivation doesnt allocate at all.
+use std::any::Any;
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::sync::OnceLock;
+use std::sync::RwLock;
+
+use crate::And;
+use crate::Dispatch;
+use crate::Not;
+use crate::Nothing;
+use crate::Or;
+use crate::PhantomToken;
+use crate::Xor;
+
+type Cache = RwLock<HashMap<(TypeId, TypeId), &'static (dyn Any + Send + Sync)>>;
+
+/// Memoized equivalent of [`Dispatch`]. The `Permissions` derive implements this for the
+/// generated permission structs with a dedicated static per struct, while the combinators fall
+/// back to the default implementation which stores the sets in a global map keyed by type.
+pub trait CachedDispatch<Z>: Dispatch<Z> + 'static
+where
+    Z: Sized + Hash + Eq + Send + Sync + 'static,
+{
+    /// Returns the `dispatch` set of this type. The set is only computed the first time this is
+    /// called, every subsequent call returns the same reference without allocating.
+    fn cached_dispatch() -> &'static HashSet<Z> {
+        global_dispatch::<Self, Z>()
+    }
+
+    /// Same as [`Dispatch::check_match`] except that it compares against the cached set.
+    fn check_match_cached(ops: &HashSet<Z>) -> bool {
+        ops.is_superset(Self::cached_dispatch())
+    }
+
+    /// Same as [`Dispatch::try_into_token`] except that it compares against the cached set.
+    fn try_into_token_cached(ops: &HashSet<Z>) -> Option<PhantomToken<Self>> {
+        if Self::check_match_cached(ops) {
+            Some(unsafe { PhantomToken::new_unchecked() })
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the `dispatch` set of `T` from the cache shared by every type without a dedicated
+/// static, computing it on first use. Backs [`CachedDispatch::cached_dispatch`] and
+/// [`Dispatch::dispatch_ref`].
+pub(crate) fn global_dispatch<T, Z>() -> &'static HashSet<Z>
+where
+    T: ?Sized + Dispatch<Z> + 'static,
+    Z: Sized + Hash + Eq + Send + Sync + 'static,
+{
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(Default::default);
+    let key = (TypeId::of::<T>(), TypeId::of::<Z>());
+
+    if let Some(set) = cache.read().unwrap().get(&key) {
+        return set.downcast_ref().expect("cache entries are keyed by type");
+    }
+
+    let set: &'static HashSet<Z> = Box::leak(Box::new(T::dispatch()));
+    cache
+        .write()
+        .unwrap()
+        .entry(key)
+        .or_insert(set)
+        .downcast_ref()
+        .expect("cache entries are keyed by type")
+}
+
+impl<Z, T, U> CachedDispatch<Z> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone + Send + Sync + 'static,
+    T: ?Sized + CachedDispatch<Z>,
+    U: ?Sized + CachedDispatch<Z>,
+{
+    fn check_match_cached(ops: &HashSet<Z>) -> bool {
+        T::check_match_cached(ops) && U::check_match_cached(ops)
+    }
+}
+
+impl<Z, T, U> CachedDispatch<Z> for Or<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone + Send + Sync + 'static,
+    T: ?Sized + CachedDispatch<Z>,
+    U: ?Sized + CachedDispatch<Z>,
+{
+    fn check_match_cached(ops: &HashSet<Z>) -> bool {
+        T::check_match_cached(ops) || U::check_match_cached(ops)
+    }
+}
+
+impl<Z, T, U> CachedDispatch<Z> for Xor<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone + Send + Sync + 'static,
+    T: ?Sized + CachedDispatch<Z>,
+    U: ?Sized + CachedDispatch<Z>,
+{
+    fn check_match_cached(ops: &HashSet<Z>) -> bool {
+        T::check_match_cached(ops) != U::check_match_cached(ops)
+    }
+}
+
+impl<Z, T> CachedDispatch<Z> for Not<Z, T>
+where
+    Z: Sized + Hash + Eq + Clone + Send + Sync + 'static,
+    T: ?Sized + CachedDispatch<Z>,
+{
+    fn check_match_cached(ops: &HashSet<Z>) -> bool {
+        !T::check_match_cached(ops)
+    }
+}
+
+impl<Z> CachedDispatch<Z> for Nothing<Z> where Z: Sized + Hash + Eq + Send + Sync + 'static {}
@@ -0,0 +1,61 @@
+//! Object safe permission checks. [`Dispatch`] only has associated functions and returns tokens of
+//! `Self`, thus it cant be used as a trait object. [`DynDispatch`] exposes the checks through
+//! methods instead, so checkers of different permission types can be stored together.
+use core::hash::Hash;
+
+use crate::Dispatch;
+use crate::HashSet;
+
+/// Object safe companion of [`Dispatch`], implemented for every type implementing `Dispatch`.
+/// Trait objects only prove that a check exists, not that it passed, thus this doesnt derive
+/// tokens.
+///
+/// Generated permission structs are unit structs and can be boxed as is, combinators like `And`
+/// cant be constructed, box a `PhantomData` of them instead.
+///
+/// Unsized permission types, like the `dyn TCanRead` trait objects generated for every variant,
+/// implement it too, thus a `&dyn TCanRead` can be checked through `dyn_check` directly.
+///
+/// # Example
+/// ```
+/// use std::marker::PhantomData;
+/// use type_permissions::And;
+/// use type_permissions::DynDispatch;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     CanRead,
+///     CanWrite,
+/// }
+///
+/// let checks: Vec<Box<dyn DynDispatch<Permissions>>> = vec![
+///     Box::new(CanRead),
+///     Box::new(PhantomData::<And<Permissions, CanRead, CanWrite>>),
+/// ];
+///
+/// let roles = [Permissions::CanRead].iter().cloned().collect();
+/// assert!(checks[0].dyn_check(&roles));
+/// assert!(!checks[1].dyn_check(&roles));
+/// ```
+pub trait DynDispatch<Z: Sized + Hash + Eq> {
+    /// Same as [`Dispatch::dispatch`].
+    fn dyn_dispatch(&self) -> HashSet<Z>;
+
+    /// Same as [`Dispatch::check_match`].
+    fn dyn_check(&self, ops: &HashSet<Z>) -> bool;
+}
+
+impl<Z, T> DynDispatch<Z> for T
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+{
+    fn dyn_dispatch(&self) -> HashSet<Z> {
+        T::dispatch()
+    }
+
+    fn dyn_check(&self, ops: &HashSet<Z>) -> bool {
+        T::check_match(ops)
+    }
+}
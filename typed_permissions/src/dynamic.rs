@@ -0,0 +1,82 @@
+//! Requirements which are only known at runtime, for example loaded from a config file, and thus
+//! cant be expressed as a permission type.
+use core::hash::Hash;
+
+use crate::HashSet;
+
+/// How the permissions of a [`DynamicRequirement`] combine, the runtime equivalent of `And` and
+/// `Or`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequirementMode {
+    /// Every permission is required.
+    All,
+    /// Any one of the permissions is enough.
+    Any,
+}
+
+/// Set of permissions required at runtime. Unlike permission types this doesnt produce a
+/// `PhantomToken`, it only answers whether a role set satisfies it. Permissions are compared as
+/// is, thus implications declared with `#[implies(...)]` are not taken into account.
+///
+/// # Example
+/// ```
+/// use type_permissions::DynamicRequirement;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+/// enum Permissions {
+///     Read,
+///     Write,
+/// }
+///
+/// let requirement = DynamicRequirement::any(vec![Permissions::Read, Permissions::Write]);
+/// let roles = [Permissions::Write].iter().cloned().collect();
+/// assert!(requirement.check(&roles));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynamicRequirement<Z: Hash + Eq> {
+    required: HashSet<Z>,
+    mode: RequirementMode,
+}
+
+impl<Z: Hash + Eq> DynamicRequirement<Z> {
+    /// Creates a requirement combining `required` according to `mode`.
+    pub fn new(required: HashSet<Z>, mode: RequirementMode) -> Self {
+        Self { required, mode }
+    }
+
+    /// Creates a requirement satisfied by role sets holding every one of `required`.
+    pub fn all(required: impl IntoIterator<Item = Z>) -> Self {
+        Self::new(required.into_iter().collect(), RequirementMode::All)
+    }
+
+    /// Creates a requirement satisfied by role sets holding any one of `required`.
+    pub fn any(required: impl IntoIterator<Item = Z>) -> Self {
+        Self::new(required.into_iter().collect(), RequirementMode::Any)
+    }
+
+    /// Adds `perm` to the required permissions.
+    pub fn with(mut self, perm: Z) -> Self {
+        self.required.insert(perm);
+        self
+    }
+
+    /// Returns the required permissions.
+    pub fn required(&self) -> &HashSet<Z> {
+        &self.required
+    }
+
+    /// Returns how the required permissions combine.
+    pub fn mode(&self) -> RequirementMode {
+        self.mode
+    }
+
+    /// Checks whether `ops` satisfies this requirement. Like an empty `And` chain, requiring all
+    /// of no permissions is always satisfied, while any of no permissions never is.
+    pub fn check(&self, ops: &HashSet<Z>) -> bool {
+        match self.mode {
+            RequirementMode::All => ops.is_superset(&self.required),
+            RequirementMode::Any => !ops.is_disjoint(&self.required),
+        }
+    }
+}
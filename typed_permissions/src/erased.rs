@@ -0,0 +1,83 @@
+//! Runtime erased tokens. A `PhantomToken<T>` carries its permissions in its type, which makes
+//! storing tokens of different permission types together impossible. [`DynToken`] trades the
+//! type for the set of permissions the token proved, from which typed tokens can be derived again
+//! later on.
+use core::hash::Hash;
+
+use crate::And;
+use crate::Dispatch;
+use crate::HashSet;
+use crate::Nothing;
+use crate::PhantomToken;
+
+/// Marker for permission types whose whole `dispatch` set is held by any role set a token of the
+/// type was derived from. This is true for the generated permission structs and `And` chains of
+/// them, but not for `Or`, `Xor` and `Not` whose `dispatch` sets only list the permissions
+/// involved in the check. Only these types can be erased into a [`DynToken`] as otherwise the
+/// erased token would carry permissions that were never granted.
+pub trait Erasable<Z: Sized + Hash + Eq>: Dispatch<Z> {}
+
+impl<Z, T, U> Erasable<Z> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Erasable<Z>,
+    U: ?Sized + Erasable<Z>,
+{
+}
+
+impl<Z> Erasable<Z> for Nothing<Z> where Z: Sized + Hash + Eq {}
+
+/// Type erased token which carries the set of permissions proven by the `PhantomToken` it was
+/// erased from. See [`PhantomToken::erase`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DynToken<Z: Hash + Eq> {
+    roles: HashSet<Z>,
+}
+
+impl<Z: Hash + Eq> DynToken<Z> {
+    /// Returns the set of permissions carried by this token.
+    pub fn roles(&self) -> &HashSet<Z> {
+        &self.roles
+    }
+
+    /// Re-checks the carried permissions against `U`, returning a typed token if they satisfy it.
+    pub fn try_into<U: ?Sized + Dispatch<Z>>(&self) -> Option<PhantomToken<U>> {
+        U::try_into_token(&self.roles)
+    }
+}
+
+impl<T: ?Sized> PhantomToken<T> {
+    /// Erases the type of this token, keeping the permissions it proves around at runtime. This
+    /// is useful for storing tokens of different permission types in the same collection.
+    ///
+    /// # Example
+    /// ```
+    /// use type_permissions::And;
+    /// use type_permissions::Dispatch;
+    /// use type_permissions::Permissions;
+    ///
+    /// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+    /// enum Permissions {
+    ///     CanRead,
+    ///     CanWrite,
+    /// }
+    ///
+    /// let roles = [Permissions::CanRead, Permissions::CanWrite].iter().cloned().collect();
+    /// let tokens = vec![
+    ///     CanRead::try_into_token(&roles).unwrap().erase(),
+    ///     And::<Permissions, CanRead, CanWrite>::try_into_token(&roles).unwrap().erase(),
+    /// ];
+    ///
+    /// assert!(tokens[0].try_into::<CanWrite>().is_none());
+    /// assert!(tokens[1].try_into::<CanWrite>().is_some());
+    /// ```
+    pub fn erase<Z>(self) -> DynToken<Z>
+    where
+        T: Erasable<Z>,
+        Z: Sized + Hash + Eq,
+    {
+        DynToken {
+            roles: T::dispatch(),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Type level subset relationships between permission types. A `PhantomToken<T>` can be weakened
+//! into a `PhantomToken<U>` whenever `T: Implies<U, I>`, for example a token carrying
+//! `And<Z, A, B>` is usable anywhere only `A` or only `B` is required.
+//!
+//! The `I` parameter is an index which records where in `T` the permission `U` was found. It
+//! exists purely to keep the impls from overlapping (think `And<Z, A, A>`) and is always inferred,
+//! thus you will usually write `token.weaken::<A, _>()`.
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use crate::And;
+use crate::Dispatch;
+
+/// Index for a type implying itself, or the generated `All` permission implying a single
+/// permission.
+pub struct Here;
+/// Index for a permission found in the left operand of an `And`.
+pub struct Left<I>(PhantomData<I>);
+/// Index for a permission found in the right operand of an `And`.
+pub struct Right<I>(PhantomData<I>);
+
+pub(crate) mod private {
+    /// # Safety
+    /// Implementing this allows [`PhantomToken::weaken`](crate::PhantomToken::weaken) to turn a
+    /// token of `Self` into a token of `U`, thus it must only be implemented when every role set
+    /// satisfying `Self` also satisfies `U`. The derive is the only intended implementor.
+    pub unsafe trait Sealed<U: ?Sized, I> {}
+}
+
+/// Sealed trait meaning that a token satisfying `Self` also satisfies `U`. Every type implies
+/// itself and an `And` implies anything either of its operands implies.
+///
+/// The supertrait is an `unsafe trait` reexported for the derive, thus an impl written by hand
+/// outside of this crate needs an `unsafe impl`, the same way minting a token does with
+/// [`PhantomToken::new_unchecked`](crate::PhantomToken::new_unchecked).
+pub trait Implies<U: ?Sized, I>: private::Sealed<U, I> {}
+
+unsafe impl<T: ?Sized> private::Sealed<T, Here> for T {}
+impl<T: ?Sized> Implies<T, Here> for T {}
+
+unsafe impl<Z, T, U, V, I> private::Sealed<V, Left<I>> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + Implies<V, I>,
+    U: ?Sized + Dispatch<Z>,
+    V: ?Sized,
+{
+}
+
+impl<Z, T, U, V, I> Implies<V, Left<I>> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + Implies<V, I>,
+    U: ?Sized + Dispatch<Z>,
+    V: ?Sized,
+{
+}
+
+unsafe impl<Z, T, U, V, I> private::Sealed<V, Right<I>> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z> + Implies<V, I>,
+    V: ?Sized,
+{
+}
+
+impl<Z, T, U, V, I> Implies<V, Right<I>> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z> + Implies<V, I>,
+    V: ?Sized,
+{
+}
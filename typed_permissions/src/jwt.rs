@@ -0,0 +1,137 @@
+//! [jsonwebtoken](https://docs.rs/jsonwebtoken) integration. Derives a `PhantomToken` straight
+//! from a signed JWT carrying the granted permissions in one of its claims.
+//!
+//! # Example
+//! ```
+//! use jsonwebtoken::DecodingKey;
+//! use type_permissions::jwt::token_from_jwt;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) {}
+//!
+//! // Signed with `secret`, carries a `"permissions": ["CanDeleteUsers"]` claim.
+//! let jwt = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.\
+//!            eyJleHAiOjQxMDI0NDQ4MDAsInBlcm1pc3Npb25zIjpbIkNhbkRlbGV0ZVVzZXJzIl19.\
+//!            47hRVPGybo3JPPh14joZPQaZRKmcy_qw6k7b4N_cxL8";
+//! let key = DecodingKey::from_secret(b"secret");
+//! delete_users(token_from_jwt::<Permissions, CanDeleteUsers>(jwt, &key).unwrap());
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use jsonwebtoken::DecodingKey;
+use jsonwebtoken::Validation;
+use serde_json::Value;
+
+use crate::Dispatch;
+use crate::PermissionError;
+use crate::PhantomToken;
+use crate::RoleSetBuilder;
+
+/// Error returned when a token couldnt be derived from a JWT.
+#[derive(Debug)]
+pub enum JwtPermError<Z: Hash + Eq> {
+    /// The JWT failed to decode or validate.
+    Jwt(jsonwebtoken::errors::Error),
+    /// The permissions claim is missing or isnt an array of strings.
+    InvalidClaim(String),
+    /// The permissions in the claim didnt satisfy the permission type.
+    Denied(PermissionError<Z>),
+}
+
+impl<Z: Hash + Eq + fmt::Display> fmt::Display for JwtPermError<Z> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Jwt(e) => write!(f, "invalid jwt: {}", e),
+            Self::InvalidClaim(claim) => {
+                write!(f, "claim `{}` is missing or not an array of strings", claim)
+            }
+            Self::Denied(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<Z: Hash + Eq + fmt::Debug + fmt::Display + 'static> Error for JwtPermError<Z> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Jwt(e) => Some(e),
+            Self::InvalidClaim(_) => None,
+            Self::Denied(e) => Some(e),
+        }
+    }
+}
+
+/// Decodes JWTs and derives tokens from the permissions listed in one of their claims. Claims
+/// which dont name a permission are ignored, so newer issuers can hand out permissions older
+/// services dont know about yet.
+#[derive(Debug, Clone)]
+pub struct JwtDecoder {
+    claim: String,
+    validation: Validation,
+}
+
+impl JwtDecoder {
+    /// Creates a decoder reading the `permissions` claim and validating JWTs with `validation`.
+    pub fn new(validation: Validation) -> Self {
+        Self {
+            claim: "permissions".to_string(),
+            validation,
+        }
+    }
+
+    /// Sets the name of the claim holding the array of permissions.
+    pub fn claim(mut self, claim: impl Into<String>) -> Self {
+        self.claim = claim.into();
+        self
+    }
+
+    /// Decodes `jwt` and derives a `PhantomToken<T>` from the permissions in its claim.
+    pub fn token<Z, T>(
+        &self,
+        jwt: &str,
+        key: &DecodingKey,
+    ) -> Result<PhantomToken<T>, JwtPermError<Z>>
+    where
+        Z: Hash + Eq + Clone + FromStr,
+        T: ?Sized + Dispatch<Z>,
+    {
+        let claims = jsonwebtoken::decode::<Value>(jwt, key, &self.validation)
+            .map_err(JwtPermError::Jwt)?
+            .claims;
+
+        let permissions = claims
+            .get(&self.claim)
+            .and_then(Value::as_array)
+            .and_then(|x| x.iter().map(Value::as_str).collect::<Option<Vec<_>>>())
+            .ok_or_else(|| JwtPermError::InvalidClaim(self.claim.clone()))?;
+
+        T::require(&RoleSetBuilder::from_claims(permissions).build()).map_err(JwtPermError::Denied)
+    }
+}
+
+impl Default for JwtDecoder {
+    fn default() -> Self {
+        Self::new(Validation::default())
+    }
+}
+
+/// Decodes `jwt` with the default [`JwtDecoder`], which validates HS256 signed JWTs including
+/// their `exp` claim and reads the permissions from the `permissions` claim.
+pub fn token_from_jwt<Z, T>(
+    jwt: &str,
+    key: &DecodingKey,
+) -> Result<PhantomToken<T>, JwtPermError<Z>>
+where
+    Z: Hash + Eq + Clone + FromStr,
+    T: ?Sized + Dispatch<Z>,
+{
+    JwtDecoder::default().token(jwt, key)
+}
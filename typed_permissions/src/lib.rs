@@ -12,9 +12,12 @@
 //!
 //! # Example
 //! ```
+//! use type_permissions::requires;
+//! use type_permissions::And;
 //! use type_permissions::Dispatch;
 //! use type_permissions::PhantomToken;
 //! use type_permissions::Permissions;
+//! use type_permissions::TAnd;
 //!
 //! /// These are our permissions that we want to derive.
 //! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
@@ -27,9 +30,12 @@
 //! // macro.
 //! fn function_x<T: ?Sized + TCanCallFunctionX>(_: PhantomToken<T>) {}
 //! fn function_y<T: ?Sized + TCanCallFunctionY>(_: PhantomToken<T>) {}
-//! fn function_xy<T: ?Sized + And<CanCallFunctionX, CanCallFunctionY>(_: PhantomToken<T>) {}
+//! fn function_xy<T: ?Sized + TAnd<Permissions, CanCallFunctionX, CanCallFunctionY>>(
+//!     _: PhantomToken<T>,
+//! ) {
+//! }
 //! // No need to manually type constrict when using the `requires` macro
-//! #[derive(Requires("CanCallFunctionX"))]
+//! #[requires("CanCallFunctionX")]
 //! fn function_x_v2() {}
 //!
 //! // In practice this function should be some function which returns `Option<PhantomToken<T>>`
@@ -38,7 +44,7 @@
 //! // `Some(..)` otherwise `None`.
 //! // For the sake of simplicity we dont do that here however you get the idea.
 //! fn get_typed_perm<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
-//!     PhantomToken::new()
+//!     unsafe { PhantomToken::new_unchecked() }
 //! }
 //!
 //! fn main() {
@@ -46,36 +52,640 @@
 //!     function_x(token);
 //!     // This wont compile because token doesnt have the `CanCallFunctionY` permission.
 //!     // function_y(token);
+//!
+//!     // The `requires` macro adds the token as the first argument.
+//!     function_x_v2(get_typed_perm::<CanCallFunctionX>());
+//!
+//!     let token: PhantomToken<And<Permissions, CanCallFunctionX, CanCallFunctionY>> =
+//!         get_typed_perm();
+//!     function_xy(token);
 //! }
 //! ```
-use std::cmp::Eq;
-use std::collections::HashSet;
-use std::hash::Hash;
-use std::marker::PhantomData;
+//!
+//! # Feature gated permissions
+//! Variants can be gated with `#[cfg(...)]`. The compiler removes disabled variants before the
+//! derive runs, thus markers, implications, groups and the `ALL`, `iter` and `registry` listings
+//! only ever cover the enabled variants. An `implies` naming a gated variant has to be gated the
+//! same way with `#[cfg_attr(..., implies(...))]`, `permissions!` does this on its own. Indices
+//! and the bits derived from them are assigned among the enabled variants, give the variants
+//! explicit discriminants together with `#[permissions(bits)]` if stored `u64` masks must not
+//! depend on the enabled features.
+//!
+//! # `no_std`
+//! With the `no_std` feature enabled the crate only depends on `core` and `alloc`, role sets are
+//! then `hashbrown::HashSet`s instead of `std::collections::HashSet`s. `CachedDispatch` and the
+//! web framework integrations need `std` and are unavailable in this mode, enabling one of them
+//! together with `no_std` is a compile error.
+//!
+//! # Tracing
+//! With the `tracing` feature enabled every token derived through [`Dispatch::try_into_token`],
+//! and thus `require` and the framework integrations, as well as every [`matches()`] check emits a
+//! `TRACE` level event recording the permission type and whether the check succeeded. Nested
+//! `check_match` calls made by combinators dont emit events of their own, so each check is only
+//! logged once. Without the feature the events are compiled out entirely.
+#![cfg_attr(feature = "no_std", no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp::Eq;
+use core::convert::TryFrom;
+#[cfg(feature = "no_std")]
+use core::error::Error;
+use core::fmt;
+use core::hash::Hash;
+use core::marker::PhantomData;
+#[cfg(not(feature = "no_std"))]
+use std::error::Error;
+
+#[cfg(all(
+    feature = "no_std",
+    any(
+        feature = "actix",
+        feature = "axum",
+        feature = "jwt",
+        feature = "rocket",
+        feature = "tower",
+        feature = "warp"
+    )
+))]
+compile_error!(
+    "the `no_std` feature cant be combined with the `actix`, `axum`, `jwt`, `rocket`, `tower` and \
+     `warp` integrations as they need `std`"
+);
+
+#[cfg(all(feature = "actix", not(feature = "no_std")))]
+pub mod actix;
+#[cfg(all(feature = "axum", not(feature = "no_std")))]
+pub mod axum;
+mod bits;
+mod bridge;
+mod builder;
+#[cfg(not(feature = "no_std"))]
+mod cache;
+mod dyn_dispatch;
+mod dynamic;
+mod erased;
+pub mod implies;
+#[cfg(all(feature = "jwt", not(feature = "no_std")))]
+pub mod jwt;
+mod mixed;
+mod name;
+pub mod order;
+#[cfg(all(feature = "rocket", not(feature = "no_std")))]
+pub mod rocket;
+#[cfg(feature = "serde")]
+pub mod serde;
+#[cfg(feature = "small")]
+mod small;
+#[cfg(all(feature = "tower", not(feature = "no_std")))]
+pub mod tower;
+mod tuple;
+#[cfg(all(feature = "warp", not(feature = "no_std")))]
+pub mod warp;
+
+pub use bits::const_check;
+pub use bits::ConstCheck;
+pub use bits::DispatchBits;
+pub use bits::PermissionBits;
+pub use bridge::PermBridge;
+pub use builder::RoleSetBuilder;
+#[cfg(not(feature = "no_std"))]
+pub use cache::CachedDispatch;
+pub use dyn_dispatch::DynDispatch;
+pub use dynamic::DynamicRequirement;
+pub use dynamic::RequirementMode;
+pub use erased::DynToken;
+pub use erased::Erasable;
+pub use implies::Implies;
+pub use mixed::AndMixed;
+pub use name::PermName;
+pub use tuple::AllOf;
+pub use tuple::AnyOf;
+pub use tuple::TupleFold;
+pub use typed_perm_derive::permissions;
+pub use typed_perm_derive::requires;
+pub use typed_perm_derive::requires_all;
+pub use typed_perm_derive::requires_any;
+pub use typed_perm_derive::requires_impl;
+pub use typed_perm_derive::Permissions;
+
+/// The set type role sets are passed around in. This is `std::collections::HashSet`, or
+/// `hashbrown::HashSet` when the `no_std` feature is enabled.
+#[cfg(feature = "no_std")]
+pub use hashbrown::HashSet;
+/// The set type role sets are passed around in. This is `std::collections::HashSet`, or
+/// `hashbrown::HashSet` when the `no_std` feature is enabled.
+#[cfg(not(feature = "no_std"))]
+pub use std::collections::HashSet;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::implies::private::Sealed as ImpliesSealed;
+    pub use crate::name::private::Sealed as PermNameSealed;
+    #[cfg(feature = "small")]
+    pub use crate::small::SmallSet;
+    pub use alloc::string::String;
+    pub use alloc::vec;
+    #[cfg(feature = "serde")]
+    pub use serde;
+    pub use typed_perm_derive::validate_permissions;
+}
+
+/// Error returned by the `FromStr` impl generated by the `Permissions` derive when the string
+/// doesnt name any of the enum's variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePermissionError {
+    name: String,
+}
+
+impl ParsePermissionError {
+    /// Creates a new error for the unknown permission `name`.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
+    /// Returns the permission name that failed to parse.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Display for ParsePermissionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown permission `{}`", self.name)
+    }
+}
+
+impl Error for ParsePermissionError {}
+
+/// Metadata of a single permission, as listed by the `registry` function generated by the
+/// `Permissions` derive when the enum is annotated with `#[permissions(registry)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PermissionMeta {
+    name: &'static str,
+    scope: &'static str,
+    description: &'static str,
+}
+
+impl PermissionMeta {
+    /// Creates the metadata of a permission, this is usually only called by the derive.
+    pub const fn new(name: &'static str, scope: &'static str, description: &'static str) -> Self {
+        Self {
+            name,
+            scope,
+            description,
+        }
+    }
+
+    /// Returns the name of the permission variant.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the string the permission is displayed and parsed as, which differs from the name
+    /// for variants renamed with `#[perm(rename = "...")]`.
+    pub fn scope(&self) -> &'static str {
+        self.scope
+    }
+
+    /// Returns the description set with `#[perm(doc = "...")]`, or an empty string.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+/// Error returned by [`Dispatch::try_into_token_verbose`] carrying the permissions that were
+/// missing from the role set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPermissions<Z: Hash + Eq> {
+    missing: HashSet<Z>,
+}
+
+impl<Z: Hash + Eq> MissingPermissions<Z> {
+    /// Creates a new error for the set of `missing` permissions.
+    pub fn new(missing: HashSet<Z>) -> Self {
+        Self { missing }
+    }
+
+    /// Returns the permissions that were missing.
+    pub fn missing(&self) -> &HashSet<Z> {
+        &self.missing
+    }
+}
+
+impl<Z: Hash + Eq + fmt::Display> fmt::Display for MissingPermissions<Z> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut missing = self
+            .missing
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>();
+        missing.sort();
+        write!(f, "missing permissions: {}", missing.join(", "))
+    }
+}
+
+impl<Z: Hash + Eq + fmt::Debug + fmt::Display> Error for MissingPermissions<Z> {}
+
+/// Error type for the `Result` returning parts of the API such as [`Dispatch::require`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PermissionError<Z: Hash + Eq> {
+    /// The role set didnt satisfy the permission type.
+    Denied {
+        /// The permissions dispatched by the permission type.
+        required: HashSet<Z>,
+        /// The required permissions which were missing from the role set.
+        missing: HashSet<Z>,
+    },
+}
+
+impl<Z: Hash + Eq + fmt::Display> fmt::Display for PermissionError<Z> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Denied { missing, .. } => {
+                let mut missing = missing.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+                missing.sort();
+                write!(f, "permission denied, missing: {}", missing.join(", "))
+            }
+        }
+    }
+}
+
+impl<Z: Hash + Eq + fmt::Debug + fmt::Display> Error for PermissionError<Z> {}
 
 /// This is a trait which is auto applied to each generated permission struct. It is used for
 /// signature dispatching. What I mean by that is that each permission struct must idenitify itself
 /// so that deriving a `PhantomToken` from a JWT claim for example is as easy as possible.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` isnt a permission type for `{T}`",
+    label = "not a permission marker or combinator over `{T}`",
+    note = "permission markers are generated by `#[derive(Permissions)]` on the enum, did you forget to derive it?"
+)]
 pub trait Dispatch<T: Sized + Hash + Eq> {
     /// This is a required function which must return a `HashSet`, the set returned usually only
     /// contains one item of type `T`. Type `T` is usually the enum that derives `Permissions`.
+    ///
+    /// The set is rebuilt on every call, hot paths which want to borrow it instead should use
+    /// [`Dispatch::dispatch_ref`].
     fn dispatch() -> HashSet<T>;
+
+    /// Returns a borrowed `dispatch` set which is computed once per type and reused by every
+    /// later call, so comparing against it doesnt allocate. The cache needs `std`, thus this is
+    /// unavailable with the `no_std` feature. [`Dispatch::check_match`] keeps building the set as
+    /// it has to work for permission types which arent `'static`.
+    #[cfg(not(feature = "no_std"))]
+    fn dispatch_ref() -> &'static HashSet<T>
+    where
+        Self: 'static,
+        T: Send + Sync + 'static,
+    {
+        cache::global_dispatch::<Self, T>()
+    }
+
+    /// Inserts the dispatched set into `set`. Combinators implement `dispatch` in terms of this so
+    /// that a whole tree of permissions fills a single set instead of allocating and unioning a
+    /// set at every node. The default implementation simply extends `set` with `dispatch`.
+    fn collect_into(set: &mut HashSet<T>) {
+        set.extend(Self::dispatch());
+    }
+
+    /// Inserts the dispatched set into an inline `SmallSet`, which the default `check_match` uses
+    /// to avoid allocating. Generated permission structs and the combinators override this, the
+    /// default implementation goes through `dispatch`.
+    #[cfg(feature = "small")]
+    #[doc(hidden)]
+    fn collect_small(set: &mut __private::SmallSet<T>) {
+        for x in Self::dispatch() {
+            set.insert(x);
+        }
+    }
     /// To limit user implementation error, the `try_into_token` method takes in a set of roles and
     /// checks if `ops` is a superset of `Self::dispatch`, if it is then a `PhantomToken` is
     /// returned otherwise `None`. In theory this method does all role checking for you and you
     /// wont need to write your own code.
     fn try_into_token(ops: &HashSet<T>) -> Option<PhantomToken<Self>> {
-        if Self::check_match(ops) {
+        let granted = Self::check_match(ops);
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            permission = core::any::type_name::<Self>(),
+            granted,
+            "derived token"
+        );
+
+        if granted {
             Some(unsafe { PhantomToken::new_unchecked() })
         } else {
             None
         }
     }
 
+    /// Same as `try_into_token` except that the roles can come from any iterator, for example a
+    /// `Vec` or a slice of roles read from a claim, which saves building a `HashSet` by hand. The
+    /// roles are still collected into a set internally.
+    fn try_into_token_from_iter<I>(ops: I) -> Option<PhantomToken<Self>>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+        T: Clone,
+    {
+        let ops = ops
+            .into_iter()
+            .map(|x| x.borrow().clone())
+            .collect::<HashSet<_>>();
+        Self::try_into_token(&ops)
+    }
+
+    /// Same as `try_into_token` except that on failure it reports which of the dispatched
+    /// permissions are missing from `ops`, which is handy for building actionable error
+    /// responses. Whether a token is derived is still decided by `try_into_token`, thus for
+    /// combinators like `Or` the missing set is the set of permissions that were involved in the
+    /// check and not found.
+    fn try_into_token_verbose(ops: &HashSet<T>) -> Result<PhantomToken<Self>, MissingPermissions<T>>
+    where
+        T: Clone,
+    {
+        Self::try_into_token(ops).ok_or_else(|| {
+            MissingPermissions::new(Self::dispatch().difference(ops).cloned().collect())
+        })
+    }
+
+    /// `Result` flavoured `try_into_token` meant to be used with `?`, for example
+    /// `let token = CanDeleteUser::require(&roles)?;`. On failure the error carries both the
+    /// required and the missing permissions.
+    fn require(ops: &HashSet<T>) -> Result<PhantomToken<Self>, PermissionError<T>>
+    where
+        T: Clone,
+    {
+        Self::try_into_token(ops).ok_or_else(|| {
+            let required = Self::dispatch();
+            let missing = required.difference(ops).cloned().collect();
+            PermissionError::Denied { required, missing }
+        })
+    }
+
+    /// Returns the smallest subset of `ops` which still satisfies this type, or `None` if `ops`
+    /// doesnt satisfy it at all. This is meant for "request these scopes" flows, where a client
+    /// should only ask for the roles it actually needs. `Or` picks the smaller of its sides and
+    /// `And` unions both of them, the default implementation drops roles from `ops` one by one for
+    /// as long as `check_match` still holds.
+    fn minimal_grant(ops: &HashSet<T>) -> Option<HashSet<T>>
+    where
+        T: Clone,
+    {
+        if !Self::check_match(ops) {
+            return None;
+        }
+
+        let mut grant = ops.clone();
+        for x in ops {
+            grant.remove(x);
+            if !Self::check_match(&grant) {
+                grant.insert(x.clone());
+            }
+        }
+
+        Some(grant)
+    }
+
     /// Checks whether a ops set matches the dispatched set of `T`.
+    #[cfg(not(feature = "small"))]
     fn check_match(ops: &HashSet<T>) -> bool {
         ops.is_superset(&Self::dispatch())
     }
+
+    /// Checks whether a ops set matches the dispatched set of `T`.
+    #[cfg(feature = "small")]
+    fn check_match(ops: &HashSet<T>) -> bool {
+        let mut set = __private::SmallSet::new();
+        Self::collect_small(&mut set);
+        set.is_subset(ops)
+    }
+
+    /// Returns the dispatched set as a sorted `Vec`. As `dispatch` returns an unordered set this
+    /// is useful whenever the permissions have to be displayed in a deterministic order, for
+    /// example in an admin UI.
+    fn required_sorted() -> Vec<T>
+    where
+        T: Ord,
+    {
+        let mut required = Self::dispatch().into_iter().collect::<Vec<_>>();
+        required.sort();
+        required
+    }
+
+    /// Returns a human readable report of which dispatched permissions `ops` holds, for example
+    /// `granted: CanRead, CanWrite; missing: CanDelete`, which helps with debugging denied
+    /// requests. Both lists are sorted and an empty list is rendered as `none`. The dispatched set
+    /// is compared as is, thus for combinators like `Or` or implied permissions the report can
+    /// list permissions as missing even though a token can be derived.
+    fn explain(ops: &HashSet<T>) -> String
+    where
+        T: fmt::Display,
+    {
+        let (mut granted, mut missing) = (Vec::new(), Vec::new());
+        for x in Self::dispatch() {
+            if ops.contains(&x) {
+                granted.push(x.to_string());
+            } else {
+                missing.push(x.to_string());
+            }
+        }
+
+        let render = |mut names: Vec<String>| {
+            names.sort();
+            if names.is_empty() {
+                "none".to_string()
+            } else {
+                names.join(", ")
+            }
+        };
+
+        alloc::format!("granted: {}; missing: {}", render(granted), render(missing))
+    }
+}
+
+/// Builds a right-nested `And` chain out of a list of permissions, the first argument is the enum
+/// the permissions dispatch over. A single permission expands to just that permission.
+///
+/// # Example
+/// ```
+/// use type_permissions::perms;
+/// use type_permissions::And;
+/// use type_permissions::Permissions;
+/// use type_permissions::PhantomToken;
+/// use type_permissions::TAnd;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     CanRead,
+///     CanWrite,
+///     CanDelete,
+/// }
+///
+/// // Same as `And<Permissions, CanRead, And<Permissions, CanWrite, CanDelete>>`.
+/// type ReadWriteDelete = perms!(Permissions; CanRead, CanWrite, CanDelete);
+///
+/// fn purge<T>(_: PhantomToken<T>)
+/// where
+///     T: ?Sized + TAnd<Permissions, CanRead, perms!(Permissions; CanWrite, CanDelete)>,
+/// {
+/// }
+///
+/// purge(unsafe { PhantomToken::<ReadWriteDelete>::new_unchecked() });
+/// ```
+#[macro_export]
+macro_rules! perms {
+    ($z:ty; $t:ty $(,)?) => { $t };
+    ($z:ty; $t:ty, $($rest:ty),+ $(,)?) => {
+        $crate::And<$z, $t, $crate::perms!($z; $($rest),+)>
+    };
+}
+
+/// Builds a right-nested `Or` chain out of a list of permissions, the first argument is the enum
+/// the permissions dispatch over. This is the `Or` equivalent of [`perms!`], useful for endpoints
+/// which are callable by any of several roles.
+///
+/// # Example
+/// ```
+/// use type_permissions::any;
+/// use type_permissions::Or;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     Admin,
+///     Moderator,
+///     Owner,
+/// }
+///
+/// // Same as `Or<Permissions, Admin, Or<Permissions, Moderator, Owner>>`.
+/// type Staff = any!(Permissions; Admin, Moderator, Owner);
+/// ```
+#[macro_export]
+macro_rules! any {
+    ($z:ty; $t:ty $(,)?) => { $t };
+    ($z:ty; $t:ty, $($rest:ty),+ $(,)?) => {
+        $crate::Or<$z, $t, $crate::any!($z; $($rest),+)>
+    };
+}
+
+/// Checks whether the role set `ops` would satisfy the permission type `T` without deriving a
+/// token, handy for things like feature gating a UI. This simply delegates to
+/// [`Dispatch::check_match`].
+///
+/// # Example
+/// ```
+/// use type_permissions::HashSet;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     CanRead,
+///     CanWrite,
+/// }
+///
+/// let roles: HashSet<_> = [Permissions::CanRead].iter().cloned().collect();
+/// assert!(type_permissions::matches::<CanRead, _>(&roles));
+/// assert!(!type_permissions::matches::<CanWrite, _>(&roles));
+/// ```
+pub fn matches<T, Z>(ops: &HashSet<Z>) -> bool
+where
+    T: ?Sized + Dispatch<Z>,
+    Z: Sized + Hash + Eq,
+{
+    let granted = T::check_match(ops);
+    #[cfg(feature = "tracing")]
+    tracing::trace!(
+        permission = core::any::type_name::<T>(),
+        granted,
+        "checked permissions"
+    );
+
+    granted
+}
+
+/// Checks a list of permission types against one role set at once, returning a `Vec<bool>` with
+/// the result of [`matches`] for each of them in order. Handy for batch authorization, such as
+/// working out which menu entries to show.
+///
+/// # Example
+/// ```
+/// use type_permissions::which_allowed;
+/// use type_permissions::HashSet;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     CanRead,
+///     CanWrite,
+///     CanDelete,
+/// }
+///
+/// let roles: HashSet<_> = [Permissions::CanRead].iter().cloned().collect();
+/// let allowed = which_allowed!(roles, [CanRead, CanWrite, CanDelete]);
+/// assert_eq!(allowed, [true, false, false]);
+/// ```
+#[macro_export]
+macro_rules! which_allowed {
+    ($ops:expr, [$($t:ty),* $(,)?]) => {{
+        let ops = &$ops;
+        $crate::__private::vec![$($crate::matches::<$t, _>(ops)),*]
+    }};
+}
+
+/// Returns an iterator over the permissions required by `T` without having to import `Dispatch`,
+/// which is handy for feeding them into logging or metrics. The order is unspecified, see
+/// [`Dispatch::required_sorted`] for a deterministic one. For the `Or` family of combinators
+/// every permission involved in the check is yielded.
+pub fn required_iter<T, Z>() -> impl Iterator<Item = Z>
+where
+    T: ?Sized + Dispatch<Z>,
+    Z: Sized + Hash + Eq,
+{
+    T::dispatch().into_iter()
+}
+
+/// Returns the space separated OAuth scope string listing the permissions of `T`, which is what a
+/// client has to request to be able to derive a token of type `T`. Each permission is written
+/// through its `Display` impl, thus renamed variants use their new name. The scopes are sorted to
+/// keep the string deterministic. For the `Or` family of combinators every permission involved in
+/// the check is listed, see [`Dispatch::dispatch`].
+///
+/// # Example
+/// ```
+/// use type_permissions::And;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// enum Permissions {
+///     #[perm(rename = "can-write-users")]
+///     CanWriteUsers,
+///     #[perm(rename = "can-read-users")]
+///     CanReadUsers,
+/// }
+///
+/// assert_eq!(
+///     type_permissions::scopes::<And<Permissions, CanWriteUsers, CanReadUsers>, _>(),
+///     "can-read-users can-write-users"
+/// );
+/// ```
+pub fn scopes<T, Z>() -> String
+where
+    T: ?Sized + Dispatch<Z>,
+    Z: Sized + Hash + Eq + fmt::Display,
+{
+    let mut scopes = T::dispatch()
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>();
+    scopes.sort();
+    scopes.join(" ")
 }
 
 /// Until variadics become a thing in rust, having functions require multiple permissions requires
@@ -89,8 +699,32 @@ pub trait Dispatch<T: Sized + Hash + Eq> {
 /// And<MyPerms, TypeA, TypeB> == And<MyPerms, TypeB, TypeA>
 /// ```
 /// What this means in practice is that when getting errors from the compiler double check that the
-/// type signature of the token and the function are the same.
-pub trait TAnd<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>, U: ?Sized + Dispatch<Z>> {}
+/// type signature of the token and the function are the same. If they only differ in order you can
+/// use [`PhantomToken::commute`], [`PhantomToken::associate_left`] and
+/// [`PhantomToken::associate_right`] to rearrange the token. Each of them rearranges a single
+/// level, deeper reorderings take several calls or [`PhantomToken::cast`].
+///
+/// At the bound level the order of the top-level operands can be ignored by making the bound
+/// generic over `O`, see the [`order`] module for details.
+///
+/// There is deliberately no canonical ordering which would make `And<Z, A, B>` and
+/// `And<Z, B, A>` the same type. Sorting operands by a per permission `const` requires evaluating
+/// generic constant expressions in types, which stable Rust cant do, and `From` conversions
+/// between the two orderings would overlap with `impl<T> From<T> for T` when both are the same
+/// type.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesnt require both `{T}` and `{U}`",
+    label = "expected a token requiring `{T}` and `{U}`",
+    note = "`TAnd` is implemented for `And` of permission markers generated by `#[derive(Permissions)]`, check that the operands and their order match"
+)]
+pub trait TAnd<
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+    O = order::Straight,
+>
+{
+}
 
 /// When you want to build a `PhantomToken` you most likely want to pass `And` as a type parameter
 /// instead of `TAnd` or `dyn TAnd`. When constricting the type requirement of some function, if
@@ -118,6 +752,14 @@ where
 {
 }
 
+impl<Z, T, U> TAnd<Z, U, T, order::Swapped> for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+}
+
 impl<Z, T, U> Dispatch<Z> for And<Z, T, U>
 where
     Z: Sized + Hash + Eq + Clone,
@@ -125,12 +767,47 @@ where
     U: ?Sized + Dispatch<Z>,
 {
     fn dispatch() -> HashSet<Z> {
-        T::dispatch().union(&U::dispatch()).cloned().collect()
+        let mut set = HashSet::new();
+        Self::collect_into(&mut set);
+        set
+    }
+
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+        U::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+        U::collect_small(set);
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops) && U::check_match(ops)
+    }
+
+    fn minimal_grant(ops: &HashSet<Z>) -> Option<HashSet<Z>> {
+        let mut grant = T::minimal_grant(ops)?;
+        grant.extend(U::minimal_grant(ops)?);
+        Some(grant)
     }
 }
 
 /// Logical or operation trait. Additionally see `And` and `TAnd`.
-pub trait TOr<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>, U: ?Sized + Dispatch<Z>> {}
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` doesnt require either of `{T}` or `{U}`",
+    label = "expected a token requiring `{T}` or `{U}`",
+    note = "`TOr` is implemented for `Or` of permission markers generated by `#[derive(Permissions)]`, check that the operands and their order match"
+)]
+pub trait TOr<
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+    O = order::Straight,
+>
+{
+}
 /// Logical or operation trait. Additionally see `And` and `TAnd`.
 pub struct Or<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>, U: ?Sized + Dispatch<Z>> {
     _z: PhantomData<Z>,
@@ -146,6 +823,14 @@ where
 {
 }
 
+impl<Z, T, U> TOr<Z, U, T, order::Swapped> for Or<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+}
+
 impl<Z, T, U> Dispatch<Z> for Or<Z, T, U>
 where
     Z: Sized + Hash + Eq + Clone,
@@ -153,23 +838,455 @@ where
     U: ?Sized + Dispatch<Z>,
 {
     fn dispatch() -> HashSet<Z> {
-        T::dispatch().union(&U::dispatch()).cloned().collect()
+        let mut set = HashSet::new();
+        Self::collect_into(&mut set);
+        set
     }
 
-    fn try_into_token(ops: &HashSet<Z>) -> Option<PhantomToken<Self>> {
-        if T::check_match(ops) || U::check_match(ops) {
-            Some(unsafe { PhantomToken::new_unchecked() })
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+        U::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+        U::collect_small(set);
+    }
+
+    // Short-circuits, thus once `T` matches neither `U` nor the union of both sides is ever built.
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops) || U::check_match(ops)
+    }
+
+    fn minimal_grant(ops: &HashSet<Z>) -> Option<HashSet<Z>> {
+        match (T::minimal_grant(ops), U::minimal_grant(ops)) {
+            (Some(left), Some(right)) if right.len() < left.len() => Some(right),
+            (left, right) => left.or(right),
+        }
+    }
+}
+
+/// Side of an `Or` satisfied by a role set, see [`Or::satisfied_branch`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Branch {
+    /// Only `T` is satisfied.
+    Left,
+    /// Only `U` is satisfied.
+    Right,
+    /// Both `T` and `U` are satisfied.
+    Both,
+}
+
+/// Token for whichever side of an `Or` a role set satisfies, see [`Or::satisfied_token`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<L, R> {
+    /// The left side `T` is satisfied.
+    Left(L),
+    /// Only the right side `U` is satisfied.
+    Right(R),
+}
+
+impl<Z, T, U> Or<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+    /// Returns which side of the `Or` `ops` satisfies, or `None` if it satisfies neither. A token
+    /// of an `Or` doesnt tell which side it was derived through, this lets code which behaves
+    /// differently depending on it find out, for example whether a caller is an admin or the
+    /// owner of a resource.
+    pub fn satisfied_branch(ops: &HashSet<Z>) -> Option<Branch> {
+        match (T::check_match(ops), U::check_match(ops)) {
+            (true, true) => Some(Branch::Both),
+            (true, false) => Some(Branch::Left),
+            (false, true) => Some(Branch::Right),
+            (false, false) => None,
+        }
+    }
+
+    /// Typed flavour of [`Or::satisfied_branch`], returns a token for the satisfied side such that
+    /// it can be passed on to functions requiring only that side. When `ops` satisfies both sides
+    /// the left one is returned.
+    pub fn satisfied_token(ops: &HashSet<Z>) -> Option<Either<PhantomToken<T>, PhantomToken<U>>> {
+        if T::check_match(ops) {
+            Some(Either::Left(unsafe { PhantomToken::new_unchecked() }))
+        } else if U::check_match(ops) {
+            Some(Either::Right(unsafe { PhantomToken::new_unchecked() }))
         } else {
             None
         }
     }
 }
 
+/// Logical xor operation trait. Additionally see `And` and `TAnd`.
+pub trait TXor<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>, U: ?Sized + Dispatch<Z>> {}
+/// Logical xor operation trait. A token of this type can only be derived from a set of roles
+/// which satisfies exactly one of `T` and `U`, but never both of them.
+///
+/// Like `Or`, `dispatch` returns the union of both sides as that is the set of permissions
+/// involved in the check, the actual exclusivity is enforced by `check_match`.
+pub struct Xor<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>, U: ?Sized + Dispatch<Z>> {
+    _z: PhantomData<Z>,
+    _t: PhantomData<T>,
+    _u: PhantomData<U>,
+}
+
+impl<Z, T, U> TXor<Z, T, U> for Xor<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+}
+
+impl<Z, T, U> Dispatch<Z> for Xor<Z, T, U>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+    fn dispatch() -> HashSet<Z> {
+        let mut set = HashSet::new();
+        Self::collect_into(&mut set);
+        set
+    }
+
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+        U::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+        U::collect_small(set);
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops) != U::check_match(ops)
+    }
+}
+
+/// Logical not operation trait. Additionally see `And` and `TAnd`.
+pub trait TNot<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>> {}
+/// Logical not operation trait. A token of this type can only be derived from a set of roles
+/// which does NOT satisfy `T`, this is useful for things like guest-only endpoints which must
+/// reject already authenticated users.
+///
+/// Keep in mind that `dispatch` for `Not` is semantically awkward as there is no set of roles
+/// that describes the absence of `T`. Thus `dispatch` returns an empty set and all the actual
+/// checking is done in `check_match`.
+pub struct Not<Z: Sized + Hash + Eq, T: ?Sized + Dispatch<Z>> {
+    _z: PhantomData<Z>,
+    _t: PhantomData<T>,
+}
+
+impl<Z, T> TNot<Z, T> for Not<Z, T>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+{
+}
+
+impl<Z, T> Dispatch<Z> for Not<Z, T>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+{
+    fn dispatch() -> HashSet<Z> {
+        HashSet::new()
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        !T::check_match(ops)
+    }
+}
+
+/// Permission which requires nothing at all. `dispatch` returns an empty set so deriving a token
+/// always succeeds. This is the identity element of `And`, `And<Z, Nothing<Z>, T>` requires
+/// exactly the same permissions as `T`, and is handy as a default for generic code which is
+/// parametrized over some permission type.
+pub struct Nothing<Z: Sized + Hash + Eq> {
+    _z: PhantomData<Z>,
+}
+
+impl<Z> Dispatch<Z> for Nothing<Z>
+where
+    Z: Sized + Hash + Eq,
+{
+    fn dispatch() -> HashSet<Z> {
+        HashSet::new()
+    }
+}
+
+/// References of permission types dispatch like the permission type itself, so that generic code
+/// bounding on `Dispatch` also accepts them.
+impl<Z, T> Dispatch<Z> for &T
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+{
+    fn dispatch() -> HashSet<Z> {
+        T::dispatch()
+    }
+
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops)
+    }
+
+    fn minimal_grant(ops: &HashSet<Z>) -> Option<HashSet<Z>>
+    where
+        Z: Clone,
+    {
+        T::minimal_grant(ops)
+    }
+}
+
+/// Boxes of permission types dispatch like the permission type itself, so that generic code
+/// bounding on `Dispatch` also accepts them.
+impl<Z, T> Dispatch<Z> for alloc::boxed::Box<T>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+{
+    fn dispatch() -> HashSet<Z> {
+        T::dispatch()
+    }
+
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops)
+    }
+
+    fn minimal_grant(ops: &HashSet<Z>) -> Option<HashSet<Z>>
+    where
+        Z: Clone,
+    {
+        T::minimal_grant(ops)
+    }
+}
+
+/// `PhantomData` of permission types dispatches like the permission type itself, for generic code
+/// which only holds on to the type of a permission.
+impl<Z, T> Dispatch<Z> for PhantomData<T>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+{
+    fn dispatch() -> HashSet<Z> {
+        T::dispatch()
+    }
+
+    fn collect_into(set: &mut HashSet<Z>) {
+        T::collect_into(set);
+    }
+
+    #[cfg(feature = "small")]
+    fn collect_small(set: &mut __private::SmallSet<Z>) {
+        T::collect_small(set);
+    }
+
+    fn check_match(ops: &HashSet<Z>) -> bool {
+        T::check_match(ops)
+    }
+
+    fn minimal_grant(ops: &HashSet<Z>) -> Option<HashSet<Z>>
+    where
+        Z: Clone,
+    {
+        T::minimal_grant(ops)
+    }
+}
+
 /// A `PhantomToken` is essentially a token which is derived from some other token but is type
 /// constricted. Functions that have typed permissions will have to take in a `PhantomToken<T>`
 /// where `T` is the stacked typed permissions list.
-pub struct PhantomToken<T: ?Sized> {
+///
+/// Tokens are `Send` and `Sync` whatever `T` is, as they never hold a value of `T`.
+///
+/// A token can additionally carry a payload `C` alongside the permission proof, for example the
+/// subject id of the claim the roles were read from, see [`PhantomToken::attach`]. It defaults to
+/// `()` so plain tokens stay zero sized.
+///
+/// The state `S` tracks whether the token is still usable. Tokens are [`Active`] by default and
+/// [`PhantomToken::revoke`] turns them into [`Revoked`] ones, for example on logout, which no
+/// longer fit parameters of type `PhantomToken<T>` or `PhantomToken<T, C>`. The state is a type
+/// level marker on the value it is applied to, it doesnt stop reuse: plain tokens and tokens with a
+/// `Copy` payload are `Copy` themselves, thus any copy made before revoking, including the binding
+/// `revoke` was called on, stays active.
+pub struct PhantomToken<T: ?Sized, C = (), S = Active> {
     _marker: PhantomData<T>,
+    _state: PhantomData<S>,
+    claims: C,
+}
+
+/// State of a [`PhantomToken`] which can be used to call permission bound functions, the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Active;
+
+/// State of a [`PhantomToken`] which was revoked through [`PhantomToken::revoke`]. Permission
+/// bound functions take active tokens, thus a revoked token cant be passed to them anymore, while
+/// other copies of the token it was made from still can.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Revoked;
+
+// NOTE: These are implemented by hand as deriving them would require `T` to implement the traits
+// too, which permission structs and `dyn` permission traits dont.
+impl<T: ?Sized, C: Clone, S> Clone for PhantomToken<T, C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            _marker: PhantomData,
+            _state: PhantomData,
+            claims: self.claims.clone(),
+        }
+    }
+}
+
+impl<T: ?Sized, C: Copy, S> Copy for PhantomToken<T, C, S> {}
+
+impl<T: ?Sized, C: fmt::Debug, S> fmt::Debug for PhantomToken<T, C, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PhantomToken<{}>", core::any::type_name::<T>())?;
+        // Zero sized payloads like the default `()` carry no information worth printing.
+        if core::mem::size_of::<C>() != 0 {
+            write!(f, "({:?})", self.claims)?;
+        }
+        Ok(())
+    }
+}
+
+/// Two tokens of the same type always carry the same permissions, thus only their payloads are
+/// compared. To compare tokens of different types use [`PhantomToken::same_permissions`].
+impl<T: ?Sized, C: PartialEq, S> PartialEq for PhantomToken<T, C, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.claims == other.claims
+    }
+}
+
+impl<T: ?Sized, C: Eq, S> Eq for PhantomToken<T, C, S> {}
+
+/// Hashes the `TypeId` of the permission type along with the payload, consistent with the
+/// `PartialEq` impl. Handy for memoizing per token setup in maps keyed by tokens.
+impl<T: ?Sized + 'static, C: Hash, S> Hash for PhantomToken<T, C, S> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        core::any::TypeId::of::<T>().hash(state);
+        self.claims.hash(state);
+    }
+}
+
+/// A token requiring nothing can always be derived, thus it can be created safely out of thin air.
+/// This is handy for plumbing a no-op token into generic APIs, for example as a default.
+impl<Z: Sized + Hash + Eq> Default for PhantomToken<Nothing<Z>> {
+    fn default() -> Self {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+}
+
+/// Standard conversion from a role set, `let token: PhantomToken<T> = (&roles).try_into()?;` is
+/// the same as `T::require(&roles)?`.
+impl<Z, T> TryFrom<&HashSet<Z>> for PhantomToken<T>
+where
+    Z: Sized + Hash + Eq + Clone,
+    T: ?Sized + Dispatch<Z>,
+{
+    type Error = PermissionError<Z>;
+
+    fn try_from(ops: &HashSet<Z>) -> Result<Self, Self::Error> {
+        T::require(ops)
+    }
+}
+
+/// Extension trait for the `Option<PhantomToken<T>>` returned by [`Dispatch::try_into_token`] and
+/// other derivations, turning it into a `Result` which can be propagated with `?`.
+pub trait PhantomTokenOptionExt<T: ?Sized> {
+    /// Converts `None` into [`PermissionError::Denied`]. The role set the token was derived from
+    /// isnt known at this point, thus every required permission is reported as missing, use
+    /// [`Dispatch::require`] instead when the exact missing permissions matter.
+    fn into_result<Z>(self) -> Result<PhantomToken<T>, PermissionError<Z>>
+    where
+        Z: Sized + Hash + Eq + Clone,
+        T: Dispatch<Z>;
+}
+
+impl<T: ?Sized> PhantomTokenOptionExt<T> for Option<PhantomToken<T>> {
+    fn into_result<Z>(self) -> Result<PhantomToken<T>, PermissionError<Z>>
+    where
+        Z: Sized + Hash + Eq + Clone,
+        T: Dispatch<Z>,
+    {
+        self.ok_or_else(|| {
+            let required = T::dispatch();
+            PermissionError::Denied {
+                missing: required.clone(),
+                required,
+            }
+        })
+    }
+}
+
+// SAFETY: A token never holds a value of `T`, `PhantomData<T>` only exists to tie the permission
+// type to the token. Thus sending or sharing a token across threads never sends or shares a `T`,
+// which keeps tokens for `dyn` permission traits and other non `Send` types usable in async tasks.
+// The payload on the other hand is held by value, thus it has to be `Send` and `Sync` itself. The
+// same goes for the state, which is only ever a marker type.
+unsafe impl<T: ?Sized, C: Send, S> Send for PhantomToken<T, C, S> {}
+
+// SAFETY: See the `Send` impl above.
+unsafe impl<T: ?Sized, C: Sync, S> Sync for PhantomToken<T, C, S> {}
+
+impl<T: ?Sized, C, S> PhantomToken<T, C, S> {
+    /// Returns the payload attached with [`PhantomToken::attach`].
+    pub fn claims(&self) -> &C {
+        &self.claims
+    }
+}
+
+impl<T: ?Sized, C> PhantomToken<T, C> {
+    /// Returns a [`Revoked`] copy of this token, for example when the session it was derived for
+    /// ends. The returned token can no longer be passed to functions taking active tokens, while
+    /// its payload stays readable through [`PhantomToken::claims`]. Plain tokens are `Copy`, thus
+    /// the token itself isnt invalidated and code holding on to it or to a copy of it can keep
+    /// using it. Revoking marks a token for the code receiving the revoked one rather than
+    /// enforcing that the permission is never used again.
+    pub fn revoke(self) -> PhantomToken<T, C, Revoked> {
+        PhantomToken {
+            _marker: PhantomData,
+            _state: PhantomData,
+            claims: self.claims,
+        }
+    }
+
+    /// Splits this token into the plain permission proof and its payload, for example to
+    /// [`weaken`](PhantomToken::weaken) the proof before attaching the payload again.
+    pub fn detach(self) -> (PhantomToken<T>, C) {
+        (
+            PhantomToken {
+                _marker: PhantomData,
+                _state: PhantomData,
+                claims: (),
+            },
+            self.claims,
+        )
+    }
 }
 
 impl<T: ?Sized> PhantomToken<T> {
@@ -182,6 +1299,244 @@ impl<T: ?Sized> PhantomToken<T> {
     pub unsafe fn new_unchecked() -> Self {
         Self {
             _marker: PhantomData,
+            _state: PhantomData,
+            claims: (),
         }
     }
+
+    /// Attaches `claims` to this token, so that functions further down which need more than the
+    /// permission proof, like the id of the subject, can read it through
+    /// [`PhantomToken::claims`].
+    ///
+    /// # Example
+    /// ```
+    /// use type_permissions::Dispatch;
+    /// use type_permissions::Permissions;
+    /// use type_permissions::PhantomToken;
+    ///
+    /// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+    /// enum Permissions {
+    ///     CanRead,
+    /// }
+    ///
+    /// struct UserId(u64);
+    ///
+    /// fn read<T: ?Sized + TCanRead>(token: PhantomToken<T, UserId>) -> u64 {
+    ///     token.claims().0
+    /// }
+    ///
+    /// let roles = [Permissions::CanRead].iter().cloned().collect();
+    /// let token = CanRead::try_into_token(&roles).unwrap().attach(UserId(7));
+    /// assert_eq!(read(token), 7);
+    /// ```
+    pub fn attach<C>(self, claims: C) -> PhantomToken<T, C> {
+        PhantomToken {
+            _marker: PhantomData,
+            _state: PhantomData,
+            claims,
+        }
+    }
+
+    /// Tries to derive a `PhantomToken` from a set of roles. This simply delegates to
+    /// [`Dispatch::try_into_token`] but saves you from having to import and name the `Dispatch`
+    /// trait.
+    ///
+    /// # Example
+    /// ```
+    /// use type_permissions::And;
+    /// use type_permissions::HashSet;
+    /// use type_permissions::Permissions;
+    /// use type_permissions::PhantomToken;
+    ///
+    /// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+    /// enum Permissions {
+    ///     CanRead,
+    ///     CanWrite,
+    /// }
+    ///
+    /// let mut roles = HashSet::new();
+    /// roles.insert(Permissions::CanRead);
+    /// assert!(PhantomToken::<CanRead>::try_new(&roles).is_some());
+    /// assert!(PhantomToken::<And<Permissions, CanRead, CanWrite>>::try_new(&roles).is_none());
+    ///
+    /// roles.insert(Permissions::CanWrite);
+    /// assert!(PhantomToken::<And<Permissions, CanRead, CanWrite>>::try_new(&roles).is_some());
+    /// ```
+    pub fn try_new<Z>(ops: &HashSet<Z>) -> Option<Self>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+    {
+        T::try_into_token(ops)
+    }
+
+    /// Weakens the token into a token for a subset of its permissions, for example a
+    /// `PhantomToken<And<Z, A, B>>` can be turned into a `PhantomToken<A>` or a `PhantomToken<B>`.
+    /// See [`Implies`] for the relationships that are supported.
+    ///
+    /// The relationships come from the impls provided by this crate and generated by the derive.
+    /// Implementing `Implies` by hand needs an `unsafe impl` of its sealed supertrait, which makes
+    /// the implementor responsible for the relationship actually holding.
+    pub fn weaken<U: ?Sized, I>(self) -> PhantomToken<U>
+    where
+        T: Implies<U, I>,
+    {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+
+    /// Returns the set of permissions a token of this type proves, without needing a token at
+    /// hand, for example `PhantomToken::<T>::required()` in generic code. See
+    /// [`PhantomToken::permissions`] for the method form.
+    pub fn required<Z>() -> HashSet<Z>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+    {
+        T::dispatch()
+    }
+
+    /// Returns the set of permissions this token carries. Unlike the `Debug` impl, which only
+    /// knows the type of the token, this resolves the actual set which makes for much more
+    /// readable test assertions.
+    pub fn permissions<Z>(&self) -> HashSet<Z>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+    {
+        T::dispatch()
+    }
+
+    /// Panics in debug builds if `ops` doesnt satisfy `T`, in release builds this is a no-op. As
+    /// `new_unchecked` can create tokens out of thin air, this is a safety net for tests which
+    /// want to make sure a token actually corresponds to the role set it was handed out for.
+    pub fn assert_valid<Z>(&self, ops: &HashSet<Z>)
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+    {
+        debug_assert!(
+            T::check_match(ops),
+            "token doesnt match the role set it was checked against"
+        );
+    }
+
+    /// Re-checks this token against `ops`, returning it back if `ops` still satisfies `T` and
+    /// `None` otherwise. Long lived tokens can use this to periodically revalidate against the
+    /// current role set of the holder, for example after one of their roles got revoked.
+    pub fn refresh<Z>(self, ops: &HashSet<Z>) -> Option<Self>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+    {
+        T::try_into_token(ops)
+    }
+
+    /// Checks whether this token carries the same effective permissions as `other`, even if
+    /// their types differ, for example `And<Z, A, B>` and `And<Z, B, A>`.
+    pub fn same_permissions<Z, U>(&self, _other: &PhantomToken<U>) -> bool
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+        U: ?Sized + Dispatch<Z>,
+    {
+        T::dispatch() == U::dispatch()
+    }
+
+    /// Casts the token into a token of `U` dispatching the same permissions, a stopgap for
+    /// mismatched `And` orderings which [`PhantomToken::commute`] and the `associate` methods dont
+    /// cover, for example `And<Z, A, And<Z, B, C>>` into `And<Z, C, And<Z, A, B>>`. Debug builds
+    /// assert that both types dispatch the same set, release builds dont check anything.
+    ///
+    /// # Safety
+    /// Like `new_unchecked` this is logically unsafe, a matching dispatched set doesnt imply
+    /// matching semantics, for example `Or<Z, A, B>` and `And<Z, A, B>` dispatch the same set. Only
+    /// cast between orderings of the same `And` chain.
+    pub unsafe fn cast<Z, U>(self) -> PhantomToken<U>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+        U: ?Sized + Dispatch<Z>,
+    {
+        debug_assert!(
+            T::dispatch() == U::dispatch(),
+            "token cast between types dispatching different permissions"
+        );
+        PhantomToken::new_unchecked()
+    }
+
+    /// Merges two independently obtained tokens into a token carrying the permissions of both,
+    /// for example a `PhantomToken<A>` and a `PhantomToken<B>` combine into a
+    /// `PhantomToken<And<Z, A, B>>`.
+    pub fn combine<Z, U>(self, _other: PhantomToken<U>) -> PhantomToken<And<Z, T, U>>
+    where
+        Z: Sized + Hash + Eq,
+        T: Dispatch<Z>,
+        U: ?Sized + Dispatch<Z>,
+    {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+}
+
+// NOTE: These can't be `From` impls as they would overlap with `impl<T> From<T> for T` whenever
+// `T` and `U` are the same type.
+impl<Z, T, U> PhantomToken<And<Z, T, U>>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+    /// Swaps the operands of the `And`, turning a `PhantomToken<And<Z, T, U>>` into a
+    /// `PhantomToken<And<Z, U, T>>`. This is safe because both types require the same permissions.
+    pub fn commute(self) -> PhantomToken<And<Z, U, T>> {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+
+    /// Decomposes the token into a token for each operand, this is the inverse of `combine`. This
+    /// is safe because a role set satisfying the `And` satisfies both of its operands.
+    pub fn split(self) -> (PhantomToken<T>, PhantomToken<U>) {
+        unsafe { (PhantomToken::new_unchecked(), PhantomToken::new_unchecked()) }
+    }
+}
+
+impl<Z, T, U, V> PhantomToken<And<Z, T, And<Z, U, V>>>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+    V: ?Sized + Dispatch<Z>,
+    And<Z, U, V>: Dispatch<Z>,
+    And<Z, T, U>: Dispatch<Z>,
+{
+    /// Regroups `And<Z, T, And<Z, U, V>>` into `And<Z, And<Z, T, U>, V>`.
+    pub fn associate_left(self) -> PhantomToken<And<Z, And<Z, T, U>, V>> {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+}
+
+impl<Z, T, U, V> PhantomToken<And<Z, And<Z, T, U>, V>>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+    V: ?Sized + Dispatch<Z>,
+    And<Z, T, U>: Dispatch<Z>,
+    And<Z, U, V>: Dispatch<Z>,
+{
+    /// Regroups `And<Z, And<Z, T, U>, V>` into `And<Z, T, And<Z, U, V>>`.
+    pub fn associate_right(self) -> PhantomToken<And<Z, T, And<Z, U, V>>> {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+}
+
+impl<Z, T, U> PhantomToken<Or<Z, T, U>>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z>,
+    U: ?Sized + Dispatch<Z>,
+{
+    /// Swaps the operands of the `Or`, turning a `PhantomToken<Or<Z, T, U>>` into a
+    /// `PhantomToken<Or<Z, U, T>>`.
+    pub fn commute(self) -> PhantomToken<Or<Z, U, T>> {
+        unsafe { PhantomToken::new_unchecked() }
+    }
 }
@@ -0,0 +1,89 @@
+//! Combination of permissions from different permission enums, for services which check roles of
+//! several domains at once, for example database and api permissions.
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Heterogeneous equivalent of [`And`](crate::And), `T` and `U` may dispatch over different
+/// permission enums. As a role set only holds roles of one enum, `AndMixed` doesnt implement
+/// [`Dispatch`], instead [`AndMixed::check_match`] takes a role set for each operand.
+///
+/// Either operand can itself be a combination of permissions of its own enum, such as `And` or
+/// `Or`. Permission enums deriving `Permissions` in the same module need the `module` option to
+/// keep their generated items apart.
+///
+/// # Example
+/// ```
+/// use type_permissions::AndMixed;
+/// use type_permissions::Permissions;
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// #[permissions(module = "db")]
+/// enum DbPerm {
+///     Query,
+/// }
+///
+/// #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+/// #[permissions(module = "api")]
+/// enum ApiPerm {
+///     Call,
+/// }
+///
+/// fn main() {
+///     let db_roles = [DbPerm::Query].iter().cloned().collect();
+///     let api_roles = [ApiPerm::Call].iter().cloned().collect();
+///
+///     let token = AndMixed::<db::Query, api::Call>::try_into_token((&db_roles, &api_roles));
+///     let (_query, _call) = token.unwrap().split();
+/// }
+/// ```
+pub struct AndMixed<T: ?Sized, U: ?Sized> {
+    _t: PhantomData<T>,
+    _u: PhantomData<U>,
+}
+
+impl<T: ?Sized, U: ?Sized> AndMixed<T, U> {
+    /// Checks whether `ops.0` satisfies `T` and `ops.1` satisfies `U`.
+    pub fn check_match<Z1, Z2>(ops: (&HashSet<Z1>, &HashSet<Z2>)) -> bool
+    where
+        Z1: Sized + Hash + Eq,
+        Z2: Sized + Hash + Eq,
+        T: Dispatch<Z1>,
+        U: Dispatch<Z2>,
+    {
+        T::check_match(ops.0) && U::check_match(ops.1)
+    }
+
+    /// Same as [`Dispatch::try_into_token`] except that it takes a role set for each operand.
+    pub fn try_into_token<Z1, Z2>(ops: (&HashSet<Z1>, &HashSet<Z2>)) -> Option<PhantomToken<Self>>
+    where
+        Z1: Sized + Hash + Eq,
+        Z2: Sized + Hash + Eq,
+        T: Dispatch<Z1>,
+        U: Dispatch<Z2>,
+    {
+        if Self::check_match(ops) {
+            Some(unsafe { PhantomToken::new_unchecked() })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: ?Sized, U: ?Sized> PhantomToken<AndMixed<T, U>> {
+    /// Decomposes the token into a token for each operand.
+    pub fn split(self) -> (PhantomToken<T>, PhantomToken<U>) {
+        unsafe { (PhantomToken::new_unchecked(), PhantomToken::new_unchecked()) }
+    }
+}
+
+impl<T: ?Sized> PhantomToken<T> {
+    /// Combines two tokens of possibly different permission enums into a token requiring both,
+    /// the heterogeneous equivalent of [`PhantomToken::combine`].
+    pub fn combine_mixed<U: ?Sized>(self, _other: PhantomToken<U>) -> PhantomToken<AndMixed<T, U>> {
+        unsafe { PhantomToken::new_unchecked() }
+    }
+}
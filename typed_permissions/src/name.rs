@@ -0,0 +1,133 @@
+//! Human readable names of permission types, handy for logging which permissions a request
+//! required.
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use core::hash::Hash;
+
+use crate::And;
+use crate::Dispatch;
+use crate::Not;
+use crate::Nothing;
+use crate::Or;
+use crate::Xor;
+
+pub(crate) mod private {
+    pub trait Sealed {}
+}
+
+/// Sealed trait naming a permission type. The generated permission structs are named after
+/// themselves, while the combinators build a composite name out of their operands, for example
+/// `And<Z, A, Or<Z, B, C>>` is named `A & (B | C)`.
+///
+/// The name of a generated permission struct is additionally available as an inherent
+/// `NAME` const. Composite names cant be `const` as building them requires concatenating
+/// strings, which isnt possible in `const` contexts on stable rust.
+pub trait PermName: private::Sealed {
+    /// Returns the name of the permission type.
+    fn name() -> String;
+
+    /// Returns the name of the permission type as an operand of a combinator. Composite names
+    /// get wrapped in parentheses to keep the precedence unambiguous.
+    #[doc(hidden)]
+    fn operand_name() -> String {
+        Self::name()
+    }
+}
+
+impl<Z, T, U> private::Sealed for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+}
+
+impl<Z, T, U> PermName for And<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+    fn name() -> String {
+        format!("{} & {}", T::operand_name(), U::operand_name())
+    }
+
+    fn operand_name() -> String {
+        format!("({})", Self::name())
+    }
+}
+
+impl<Z, T, U> private::Sealed for Or<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+}
+
+impl<Z, T, U> PermName for Or<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+    fn name() -> String {
+        format!("{} | {}", T::operand_name(), U::operand_name())
+    }
+
+    fn operand_name() -> String {
+        format!("({})", Self::name())
+    }
+}
+
+impl<Z, T, U> private::Sealed for Xor<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+}
+
+impl<Z, T, U> PermName for Xor<Z, T, U>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+    U: ?Sized + Dispatch<Z> + PermName,
+{
+    fn name() -> String {
+        format!("{} ^ {}", T::operand_name(), U::operand_name())
+    }
+
+    fn operand_name() -> String {
+        format!("({})", Self::name())
+    }
+}
+
+impl<Z, T> private::Sealed for Not<Z, T>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+{
+}
+
+impl<Z, T> PermName for Not<Z, T>
+where
+    Z: Sized + Hash + Eq,
+    T: ?Sized + Dispatch<Z> + PermName,
+{
+    fn name() -> String {
+        format!("!{}", T::operand_name())
+    }
+}
+
+impl<Z> private::Sealed for Nothing<Z> where Z: Sized + Hash + Eq {}
+
+impl<Z> PermName for Nothing<Z>
+where
+    Z: Sized + Hash + Eq,
+{
+    fn name() -> String {
+        "Nothing".to_string()
+    }
+}
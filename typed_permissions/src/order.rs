@@ -0,0 +1,28 @@
+//! Operand order of the `TAnd` and `TOr` bounds. `And<Z, T, U>` implements both `TAnd<Z, T, U>`
+//! and `TAnd<Z, U, T>`, the `O` parameter records which of the two is used to keep the impls from
+//! overlapping when `T` and `U` are the same type.
+//!
+//! `O` defaults to [`Straight`], thus a plain `T: TAnd<Z, A, B>` bound only accepts `And<Z, A, B>`.
+//! Making the bound generic over the order accepts both orderings:
+//! ```
+//! use type_permissions::And;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//! use type_permissions::TAnd;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanRead,
+//!     CanWrite,
+//! }
+//!
+//! fn read_write<T: ?Sized + TAnd<Permissions, CanRead, CanWrite, O>, O>(_: PhantomToken<T>) {}
+//!
+//! read_write(unsafe { PhantomToken::<And<Permissions, CanRead, CanWrite>>::new_unchecked() });
+//! read_write(unsafe { PhantomToken::<And<Permissions, CanWrite, CanRead>>::new_unchecked() });
+//! ```
+
+/// Order index for a bound listing the operands in the order of the type.
+pub struct Straight;
+/// Order index for a bound listing the operands in reverse order of the type.
+pub struct Swapped;
@@ -0,0 +1,157 @@
+//! [rocket](https://docs.rs/rocket) integration. The [`PermissionFairing`] decodes the role set of
+//! every request from its `authorization` header into the request local cache, from which the
+//! [`TypedPerm`] request guard derives a `PhantomToken`.
+//!
+//! # Example
+//! ```no_run
+//! use std::collections::HashSet;
+//!
+//! use rocket::get;
+//! use rocket::routes;
+//! use type_permissions::rocket::PermissionFairing;
+//! use type_permissions::rocket::TypedPerm;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) {}
+//!
+//! // Requests reaching this route without `CanDeleteUsers` are rejected with `403 Forbidden`.
+//! #[get("/admin")]
+//! fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) -> &'static str {
+//!     delete_users(perm.into_inner());
+//!     "deleted"
+//! }
+//!
+//! // In practice the header would be a JWT verified with `type_permissions::jwt::JwtDecoder`.
+//! let fairing = PermissionFairing::new(|header: &str| {
+//!     header
+//!         .split(',')
+//!         .map(|x| x.parse().ok())
+//!         .collect::<Option<HashSet<Permissions>>>()
+//! });
+//!
+//! let _ = rocket::build().attach(fairing).mount("/", routes![admin]);
+//! ```
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::ops::Deref;
+
+use rocket::async_trait;
+use rocket::fairing::Fairing;
+use rocket::fairing::Info;
+use rocket::fairing::Kind;
+use rocket::http::Status;
+use rocket::request::FromRequest;
+use rocket::request::Outcome;
+use rocket::Data;
+use rocket::Request;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Role set of a request as stored in the request local cache by the [`PermissionFairing`],
+/// `None` if the request carried no valid header.
+struct CachedRoles<Z>(Option<HashSet<Z>>);
+
+/// Decodes a header value into a role set.
+type Decode<Z> = dyn Fn(&str) -> Option<HashSet<Z>> + Send + Sync;
+
+/// Fairing which decodes the role set of each request from a header, `authorization` unless
+/// changed with [`PermissionFairing::header`], and stores it in the request local cache for
+/// [`TypedPerm`] guards.
+pub struct PermissionFairing<Z> {
+    header: &'static str,
+    decode: Box<Decode<Z>>,
+}
+
+impl<Z> PermissionFairing<Z> {
+    /// Creates a fairing which decodes the header value with `decode`. Returning `None` rejects
+    /// every guarded route of the request.
+    pub fn new<F>(decode: F) -> Self
+    where
+        F: Fn(&str) -> Option<HashSet<Z>> + Send + Sync + 'static,
+    {
+        Self {
+            header: "authorization",
+            decode: Box::new(decode),
+        }
+    }
+
+    /// Sets the header the role set is decoded from.
+    pub fn header(mut self, header: &'static str) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+#[async_trait]
+impl<Z> Fairing for PermissionFairing<Z>
+where
+    Z: Send + Sync + 'static,
+{
+    fn info(&self) -> Info {
+        Info {
+            name: "Permissions",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, _: &mut Data<'_>) {
+        let roles = req.headers().get_one(self.header).and_then(&self.decode);
+        req.local_cache(|| CachedRoles(roles));
+    }
+}
+
+/// Request guard which derives a `PhantomToken<T>` from the role set stored by the
+/// [`PermissionFairing`]. Requests fail with `403 Forbidden` if the fairing stored no role set or
+/// the roles dont satisfy `T`.
+pub struct TypedPerm<Z, T: ?Sized> {
+    token: PhantomToken<T>,
+    _z: PhantomData<fn() -> Z>,
+}
+
+impl<Z, T: ?Sized> TypedPerm<Z, T> {
+    /// Returns the derived `PhantomToken`.
+    pub fn into_inner(self) -> PhantomToken<T> {
+        self.token
+    }
+}
+
+impl<Z, T: ?Sized> Deref for TypedPerm<Z, T> {
+    type Target = PhantomToken<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.token
+    }
+}
+
+#[async_trait]
+impl<'r, Z, T> FromRequest<'r> for TypedPerm<Z, T>
+where
+    Z: Sized + Hash + Eq + Send + Sync + 'static,
+    T: ?Sized + Dispatch<Z>,
+{
+    type Error = ();
+
+    async fn from_request(req: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = req
+            .local_cache(|| CachedRoles::<Z>(None))
+            .0
+            .as_ref()
+            .and_then(T::try_into_token);
+
+        match token {
+            Some(token) => Outcome::Success(TypedPerm {
+                token,
+                _z: PhantomData,
+            }),
+            None => Outcome::Error((Status::Forbidden, ())),
+        }
+    }
+}
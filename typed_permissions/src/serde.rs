@@ -0,0 +1,86 @@
+//! [serde](https://docs.rs/serde) support for persisting tokens, for example in a session. A
+//! token is serialized as the dispatch set of its permission type and deserializing checks that
+//! the set satisfies the token type.
+//!
+//! The payload is nothing but a list of permission names, anyone able to write it can list
+//! whatever permissions they like. A deserialized token is thus only as trustworthy as the place
+//! it was read from, such as a signed cookie or a server side session store. Payloads from
+//! untrusted sources should instead be rechecked against the live roles of the caller, for
+//! example with [`PhantomToken::refresh`](crate::PhantomToken::refresh).
+//!
+//! `PhantomToken<T>` doesnt name the permission enum, so it cant implement `Serialize` itself, and
+//! the permission type cant be recovered from the serialized set, so tokens are always
+//! deserialized at a known type. Both functions are meant to be used through
+//! `#[serde(serialize_with = "..", deserialize_with = "..")]`.
+//!
+//! # Example
+//! ```
+//! use serde::Deserialize;
+//! use serde::Serialize;
+//! use type_permissions::Dispatch;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+//! enum Permissions {
+//!     CanRead,
+//!     CanWrite,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Session {
+//!     #[serde(
+//!         serialize_with = "type_permissions::serde::serialize_token::<Permissions, CanRead, _>",
+//!         deserialize_with = "type_permissions::serde::deserialize_token::<Permissions, CanRead, _>"
+//!     )]
+//!     token: PhantomToken<CanRead>,
+//! }
+//!
+//! let roles = [Permissions::CanRead].iter().cloned().collect();
+//! let session = Session {
+//!     token: CanRead::try_into_token(&roles).unwrap(),
+//! };
+//!
+//! let json = serde_json::to_string(&session).unwrap();
+//! assert_eq!(json, r#"{"token":["CanRead"]}"#);
+//! assert!(serde_json::from_str::<Session>(&json).is_ok());
+//! assert!(serde_json::from_str::<Session>(r#"{"token":["CanWrite"]}"#).is_err());
+//! ```
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use serde::de::Error;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Serializes `token` as the dispatch set of `T`.
+pub fn serialize_token<Z, T, S>(_token: &PhantomToken<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    Z: Hash + Eq + Serialize,
+    T: ?Sized + Dispatch<Z>,
+    S: Serializer,
+{
+    serializer.collect_seq(T::dispatch())
+}
+
+/// Deserializes a set of permissions and derives a token of type `T` from it. This fails if the
+/// set doesnt satisfy `T`, it doesnt verify where the set came from, see the module docs.
+pub fn deserialize_token<'de, Z, T, D>(deserializer: D) -> Result<PhantomToken<T>, D::Error>
+where
+    Z: Hash + Eq + Deserialize<'de>,
+    T: ?Sized + Dispatch<Z>,
+    D: Deserializer<'de>,
+{
+    let roles = Vec::<Z>::deserialize(deserializer)?
+        .into_iter()
+        .collect::<HashSet<Z>>();
+
+    T::try_into_token(&roles)
+        .ok_or_else(|| D::Error::custom("serialized permissions dont satisfy the token type"))
+}
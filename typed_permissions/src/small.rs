@@ -0,0 +1,50 @@
+//! Inline set used by the `small` feature to check permissions without touching the heap. Most
+//! permission types only dispatch a handful of variants, for which a `HashSet` allocation is pure
+//! overhead when all that is needed is a superset check against the role set.
+use core::hash::Hash;
+
+use smallvec::SmallVec;
+
+use crate::HashSet;
+
+/// Number of permissions a [`SmallSet`] holds before spilling onto the heap.
+const INLINE: usize = 4;
+
+/// Set of permissions stored inline for up to four elements. Membership is a linear scan, which
+/// beats hashing at these sizes.
+#[doc(hidden)]
+pub struct SmallSet<Z> {
+    inner: SmallVec<[Z; INLINE]>,
+}
+
+impl<Z: Hash + Eq> SmallSet<Z> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Self {
+            inner: SmallVec::new(),
+        }
+    }
+
+    /// Inserts `value` unless it is already held.
+    pub fn insert(&mut self, value: Z) {
+        if !self.inner.contains(&value) {
+            self.inner.push(value);
+        }
+    }
+
+    /// Returns whether every element of this set is contained in `ops`.
+    pub fn is_subset(&self, ops: &HashSet<Z>) -> bool {
+        self.inner.iter().all(|x| ops.contains(x))
+    }
+
+    /// Converts this set into a `HashSet`.
+    pub fn into_set(self) -> HashSet<Z> {
+        self.inner.into_iter().collect()
+    }
+}
+
+impl<Z: Hash + Eq> Default for SmallSet<Z> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,84 @@
+//! [tower](https://docs.rs/tower) middleware which puts the role set of a request into its
+//! extensions. The role set is decoded once per request, for example from a JWT, and then picked
+//! up by the web framework extractors such as `axum::TypedPerm` to derive tokens.
+//!
+//! # Example
+//! ```no_run
+//! use std::collections::HashSet;
+//! use type_permissions::tower::PermissionLayer;
+//! use type_permissions::Permissions;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! let layer = PermissionLayer::new(|req: &http::Request<()>| {
+//!     let mut roles = HashSet::new();
+//!     if req.headers().contains_key("x-admin") {
+//!         roles.insert(Permissions::CanDeleteUsers);
+//!     }
+//!     roles
+//! });
+//! ```
+use std::task::Context;
+use std::task::Poll;
+
+use http::Request;
+use tower::Layer;
+use tower::Service;
+
+use crate::HashSet;
+
+/// Layer which decodes the role set of every request with `F` and inserts the resulting
+/// `HashSet` into the request extensions.
+#[derive(Clone)]
+pub struct PermissionLayer<F> {
+    decode: F,
+}
+
+impl<F> PermissionLayer<F> {
+    /// Creates a new layer which decodes role sets with `decode`.
+    pub fn new(decode: F) -> Self {
+        Self { decode }
+    }
+}
+
+impl<S, F: Clone> Layer<S> for PermissionLayer<F> {
+    type Service = PermissionService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PermissionService {
+            inner,
+            decode: self.decode.clone(),
+        }
+    }
+}
+
+/// Service produced by [`PermissionLayer`].
+#[derive(Clone)]
+pub struct PermissionService<S, F> {
+    inner: S,
+    decode: F,
+}
+
+impl<S, F, B, Z> Service<Request<B>> for PermissionService<S, F>
+where
+    S: Service<Request<B>>,
+    F: Fn(&Request<B>) -> HashSet<Z>,
+    Z: Clone + Send + Sync + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let roles = (self.decode)(&req);
+        req.extensions_mut().insert(roles);
+        self.inner.call(req)
+    }
+}
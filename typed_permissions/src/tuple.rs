@@ -0,0 +1,150 @@
+//! `Dispatch` impls for tuples of permission types, which read much nicer than nested `And`s. A
+//! tuple requires every one of its elements, thus `PhantomToken<(A, B, C)>` is equivalent to
+//! `PhantomToken<And<Z, A, And<Z, B, C>>>` as far as deriving the token is concerned.
+//!
+//! # Example
+//! ```
+//! use type_permissions::Dispatch;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanRead,
+//!     CanWrite,
+//! }
+//!
+//! fn audit<T: ?Sized + Dispatch<Permissions>>(_: PhantomToken<T>) {}
+//!
+//! let roles = [Permissions::CanRead, Permissions::CanWrite].iter().cloned().collect();
+//! audit(<(CanRead, CanWrite)>::try_into_token(&roles).unwrap());
+//! ```
+//!
+//! Tuples cant be used as the operands of the generated `TAnd` style bounds however, as those are
+//! written in terms of `And` and `Or`. [`AllOf`] and [`AnyOf`] fold a tuple into the equivalent
+//! right-nested `And` or `Or` chain instead, which keeps the written types flat:
+//! ```
+//! use type_permissions::AllOf;
+//! use type_permissions::AnyOf;
+//! use type_permissions::Dispatch;
+//! use type_permissions::Permissions;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+//! enum Permissions {
+//!     CanRead,
+//!     CanWrite,
+//!     CanDelete,
+//! }
+//!
+//! // Same as `And<Permissions, CanRead, And<Permissions, CanWrite, CanDelete>>`.
+//! type Purge = AllOf<Permissions, (CanRead, CanWrite, CanDelete)>;
+//! // Same as `Or<Permissions, CanWrite, CanDelete>`.
+//! type Modify = AnyOf<Permissions, (CanWrite, CanDelete)>;
+//!
+//! let roles = [Permissions::CanRead, Permissions::CanWrite].iter().cloned().collect();
+//! assert!(Purge::try_into_token(&roles).is_none());
+//! assert!(Modify::try_into_token(&roles).is_some());
+//! ```
+use core::hash::Hash;
+
+#[cfg(feature = "small")]
+use crate::__private::SmallSet;
+use crate::And;
+use crate::Dispatch;
+use crate::Erasable;
+use crate::HashSet;
+use crate::Or;
+
+/// Implements `Dispatch` and `Erasable` for a tuple of the given element type parameters. The
+/// elements must be `Sized` as tuples can only hold an unsized value in their last position.
+macro_rules! tuple_dispatch {
+    ($($t:ident),+) => {
+        impl<Z, $($t),+> Dispatch<Z> for ($($t,)+)
+        where
+            Z: Sized + Hash + Eq,
+            $($t: Dispatch<Z>,)+
+        {
+            fn dispatch() -> HashSet<Z> {
+                let mut set = HashSet::new();
+                Self::collect_into(&mut set);
+                set
+            }
+
+            fn collect_into(set: &mut HashSet<Z>) {
+                $($t::collect_into(set);)+
+            }
+
+            #[cfg(feature = "small")]
+            fn collect_small(set: &mut SmallSet<Z>) {
+                $($t::collect_small(set);)+
+            }
+
+            fn check_match(ops: &HashSet<Z>) -> bool {
+                $($t::check_match(ops))&&+
+            }
+        }
+
+        impl<Z, $($t),+> Erasable<Z> for ($($t,)+)
+        where
+            Z: Sized + Hash + Eq,
+            $($t: Erasable<Z>,)+
+        {
+        }
+    };
+}
+
+tuple_dispatch!(A, B);
+tuple_dispatch!(A, B, C);
+tuple_dispatch!(A, B, C, D);
+tuple_dispatch!(A, B, C, D, E);
+tuple_dispatch!(A, B, C, D, E, F);
+tuple_dispatch!(A, B, C, D, E, F, G);
+tuple_dispatch!(A, B, C, D, E, F, G, H);
+
+/// Folds a tuple of permission types into a right-nested `And` or `Or` chain, see [`AllOf`] and
+/// [`AnyOf`]. Implemented for tuples of up to 8 elements, a single element tuple folds into just
+/// that element.
+pub trait TupleFold<Z: Sized + Hash + Eq> {
+    /// The `And` chain requiring every element.
+    type All: ?Sized + Dispatch<Z>;
+    /// The `Or` chain requiring any of the elements.
+    type Any: ?Sized + Dispatch<Z>;
+}
+
+/// Requires every permission in the tuple `T`, short for the right-nested `And` chain built by
+/// [`perms!`](crate::perms).
+pub type AllOf<Z, T> = <T as TupleFold<Z>>::All;
+
+/// Requires any of the permissions in the tuple `T`, short for the right-nested `Or` chain built
+/// by [`any!`](crate::any).
+pub type AnyOf<Z, T> = <T as TupleFold<Z>>::Any;
+
+/// Implements `TupleFold` for the tuple of the given element type parameters and every suffix of
+/// it.
+macro_rules! tuple_fold {
+    ($t:ident) => {
+        impl<Z, $t> TupleFold<Z> for ($t,)
+        where
+            Z: Sized + Hash + Eq,
+            $t: Dispatch<Z>,
+        {
+            type All = $t;
+            type Any = $t;
+        }
+    };
+    ($t:ident, $($rest:ident),+) => {
+        impl<Z, $t, $($rest),+> TupleFold<Z> for ($t, $($rest,)+)
+        where
+            Z: Sized + Hash + Eq + Clone,
+            $t: Dispatch<Z>,
+            ($($rest,)+): TupleFold<Z>,
+        {
+            type All = And<Z, $t, <($($rest,)+) as TupleFold<Z>>::All>;
+            type Any = Or<Z, $t, <($($rest,)+) as TupleFold<Z>>::Any>;
+        }
+
+        tuple_fold!($($rest),+);
+    };
+}
+
+tuple_fold!(A, B, C, D, E, F, G, H);
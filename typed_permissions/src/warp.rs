@@ -0,0 +1,81 @@
+//! [warp](https://docs.rs/warp) integration. Routes can declare the permissions they require by
+//! composing the [`require`] filter, which derives a `PhantomToken` from the role set extracted by
+//! a prior filter.
+//!
+//! # Example
+//! ```no_run
+//! use std::collections::HashSet;
+//!
+//! use type_permissions::warp::require;
+//! use type_permissions::Permissions;
+//! use type_permissions::PhantomToken;
+//! use warp::Filter;
+//!
+//! #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+//! enum Permissions {
+//!     CanDeleteUsers,
+//! }
+//!
+//! fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) {}
+//!
+//! // In practice the role set would be decoded from a verified `authorization` header.
+//! let roles = warp::header::<String>("x-roles").map(|roles: String| {
+//!     roles
+//!         .split(',')
+//!         .filter_map(|x| x.parse().ok())
+//!         .collect::<HashSet<Permissions>>()
+//! });
+//!
+//! // Requests without `CanDeleteUsers` are rejected with a `PermissionRejection`.
+//! let admin = warp::path("admin")
+//!     .and(require::<Permissions, CanDeleteUsers, _>(roles))
+//!     .map(|token| {
+//!         delete_users(token);
+//!         "deleted"
+//!     });
+//! ```
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+use warp::reject::Reject;
+use warp::Filter;
+use warp::Rejection;
+
+use crate::Dispatch;
+use crate::HashSet;
+use crate::PhantomToken;
+
+/// Rejection returned by [`require`] when the role set doesnt satisfy the required permissions.
+/// Use `Rejection::find` in a `recover` handler to map it to a response.
+#[derive(Debug)]
+pub struct PermissionRejection {
+    _priv: (),
+}
+
+impl fmt::Display for PermissionRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("permission denied")
+    }
+}
+
+impl Error for PermissionRejection {}
+
+impl Reject for PermissionRejection {}
+
+/// Filter which derives a `PhantomToken<T>` from the role set extracted by `roles`, rejecting
+/// with a [`PermissionRejection`] when the roles dont satisfy `T`. Rejections of `roles` itself,
+/// for example a missing header, are passed through as is.
+pub fn require<Z, T, F>(
+    roles: F,
+) -> impl Filter<Extract = (PhantomToken<T>,), Error = Rejection> + Clone
+where
+    Z: Hash + Eq + Send,
+    T: Dispatch<Z> + Send + 'static,
+    F: Filter<Extract = (HashSet<Z>,), Error = Rejection> + Clone,
+{
+    roles.and_then(|roles: HashSet<Z>| async move {
+        T::try_into_token(&roles)
+            .ok_or_else(|| warp::reject::custom(PermissionRejection { _priv: () }))
+    })
+}
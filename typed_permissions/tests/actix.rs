@@ -0,0 +1,53 @@
+#![cfg(feature = "actix")]
+
+use std::collections::HashSet;
+
+use actix_web::http::StatusCode;
+use actix_web::rt::System;
+use actix_web::test;
+use actix_web::web;
+use actix_web::App;
+use actix_web::HttpMessage;
+use type_permissions::actix::TypedPerm;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanReadUsers,
+    CanDeleteUsers,
+}
+
+fn delete_users<T: ?Sized + TCanDeleteUsers>(_: &PhantomToken<T>) -> &'static str {
+    "deleted"
+}
+
+async fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) -> &'static str {
+    delete_users(&perm)
+}
+
+fn status(roles: Option<HashSet<Permissions>>) -> StatusCode {
+    System::new().block_on(async {
+        let app = test::init_service(App::new().route("/admin", web::get().to(admin))).await;
+
+        let request = test::TestRequest::get().uri("/admin").to_request();
+        if let Some(roles) = roles {
+            request.extensions_mut().insert(roles);
+        }
+
+        test::call_service(&app, request).await.status()
+    })
+}
+
+#[test]
+fn typed_perm_admits_matching_roles() {
+    let roles = [Permissions::CanDeleteUsers].iter().cloned().collect();
+    assert_eq!(status(Some(roles)), StatusCode::OK);
+}
+
+#[test]
+fn typed_perm_rejects_missing_roles() {
+    let roles = [Permissions::CanReadUsers].iter().cloned().collect();
+    assert_eq!(status(Some(roles)), StatusCode::FORBIDDEN);
+    assert_eq!(status(None), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "axum")]
+
+use std::collections::HashSet;
+
+use axum::body::Body;
+use axum::http::Request;
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::Router;
+use tower::ServiceExt;
+use type_permissions::axum::TypedPerm;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanReadUsers,
+    CanDeleteUsers,
+}
+
+fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) -> &'static str {
+    "deleted"
+}
+
+async fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) -> &'static str {
+    delete_users(perm.0)
+}
+
+async fn status(roles: Option<HashSet<Permissions>>) -> StatusCode {
+    let mut request = Request::builder().uri("/admin");
+    if let Some(roles) = roles {
+        request = request.extension(roles);
+    }
+
+    Router::new()
+        .route("/admin", get(admin))
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn typed_perm_admits_matching_roles() {
+    let roles = [Permissions::CanDeleteUsers].iter().cloned().collect();
+    assert_eq!(status(Some(roles)).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn typed_perm_rejects_missing_roles() {
+    let roles = [Permissions::CanReadUsers].iter().cloned().collect();
+    assert_eq!(status(Some(roles)).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(status(None).await, StatusCode::UNAUTHORIZED);
+}
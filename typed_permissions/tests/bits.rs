@@ -0,0 +1,171 @@
+use type_permissions::const_check;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::DispatchBits;
+use type_permissions::HashSet;
+use type_permissions::Not;
+use type_permissions::Or;
+use type_permissions::PermissionBits;
+use type_permissions::Permissions;
+use type_permissions::Xor;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Stored", bits)]
+enum StoredPermissions {
+    Read,
+    Write,
+    Delete,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Legacy", bits)]
+enum LegacyPermissions {
+    Read = 1,
+    Write = 2,
+    Delete = 0x8,
+}
+
+#[test]
+fn variants_are_assigned_consecutive_bits() {
+    assert_eq!(Permissions::Read.to_bits(), 0b001);
+    assert_eq!(Permissions::Write.to_bits(), 0b010);
+    assert_eq!(Permissions::Delete.to_bits(), 0b100);
+    assert_eq!(Read::bits(), 0b001);
+    assert_eq!(Delete::bits(), 0b100);
+}
+
+#[test]
+fn set_to_bits_folds_roles() {
+    let roles: HashSet<_> = [Permissions::Read, Permissions::Delete]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(Permissions::set_to_bits(&roles), 0b101);
+}
+
+#[test]
+fn combinators_agree_with_hash_set_checks() {
+    type ReadWrite = And<Permissions, Read, Write>;
+    type ReadOrWrite = Or<Permissions, Read, Write>;
+    type ReadXorWrite = Xor<Permissions, Read, Write>;
+    type NotDelete = Not<Permissions, Delete>;
+
+    assert_eq!(ReadWrite::bits(), 0b011);
+
+    for mask in 0..0b1000 {
+        let roles: HashSet<_> = Permissions::ALL
+            .iter()
+            .filter(|x| x.to_bits() & mask != 0)
+            .cloned()
+            .collect();
+
+        assert_eq!(
+            ReadWrite::try_into_token_bits(mask).is_some(),
+            ReadWrite::try_into_token(&roles).is_some()
+        );
+        assert_eq!(
+            ReadOrWrite::try_into_token_bits(mask).is_some(),
+            ReadOrWrite::try_into_token(&roles).is_some()
+        );
+        assert_eq!(
+            ReadXorWrite::try_into_token_bits(mask).is_some(),
+            ReadXorWrite::try_into_token(&roles).is_some()
+        );
+        assert_eq!(
+            NotDelete::try_into_token_bits(mask).is_some(),
+            NotDelete::try_into_token(&roles).is_some()
+        );
+    }
+}
+
+#[test]
+fn try_into_token_bits() {
+    assert!(And::<Permissions, Read, Write>::try_into_token_bits(0b011).is_some());
+    assert!(And::<Permissions, Read, Write>::try_into_token_bits(0b101).is_none());
+}
+
+const READ_WRITE: u128 = Permissions::Read.bit() | Permissions::Write.bit();
+
+// Evaluated at compile time, a failing check would fail the build.
+const _: () = assert!(const_check::<And<Permissions, Read, Write>, READ_WRITE>());
+const _: () = assert!(!const_check::<Delete, READ_WRITE>());
+
+#[test]
+fn const_check_matches_check_bits() {
+    const READ_ONLY: bool = const_check::<Read, { Permissions::Read.bit() }>();
+    const ALL_OF_THEM: bool = const_check::<All, READ_WRITE>();
+    const NOT_DELETE: bool = const_check::<Not<Permissions, Delete>, READ_WRITE>();
+    const EXCLUSIVE: bool = const_check::<Xor<Permissions, Read, Write>, READ_WRITE>();
+    const EITHER: bool = const_check::<Or<Permissions, Delete, Write>, READ_WRITE>();
+
+    assert_eq!(READ_ONLY, Read::check_bits(Permissions::Read.bit()));
+    assert_eq!(ALL_OF_THEM, All::check_bits(READ_WRITE));
+    assert_eq!(
+        NOT_DELETE,
+        Not::<Permissions, Delete>::check_bits(READ_WRITE)
+    );
+    assert_eq!(
+        EXCLUSIVE,
+        Xor::<Permissions, Read, Write>::check_bits(READ_WRITE)
+    );
+    assert_eq!(
+        EITHER,
+        Or::<Permissions, Delete, Write>::check_bits(READ_WRITE)
+    );
+    assert_eq!(
+        [READ_ONLY, ALL_OF_THEM, NOT_DELETE, EXCLUSIVE, EITHER],
+        [true, false, true, false, true]
+    );
+}
+
+#[test]
+fn u64_masks_round_trip() {
+    assert_eq!(StoredPermissions::Read.bit_u64(), 0b001);
+    assert_eq!(StoredPermissions::Delete.bit_u64(), 0b100);
+
+    let roles = StoredPermissions::from_bits(0b101);
+    assert_eq!(
+        roles,
+        [StoredPermissions::Read, StoredPermissions::Delete]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(StoredPermissions::set_to_bits_u64(&roles), 0b101);
+    assert!(StoredDelete::try_into_token(&roles).is_some());
+    assert!(StoredWrite::try_into_token(&roles).is_none());
+}
+
+#[test]
+fn u64_masks_ignore_unassigned_bits() {
+    assert_eq!(
+        StoredPermissions::from_bits(0b1010),
+        [StoredPermissions::Write].iter().cloned().collect()
+    );
+    assert!(StoredPermissions::from_bits(0).is_empty());
+}
+
+#[test]
+fn u64_masks_honor_explicit_discriminants() {
+    assert_eq!(LegacyPermissions::Read.bit_u64(), 0b0001);
+    assert_eq!(LegacyPermissions::Write.bit_u64(), 0b0010);
+    assert_eq!(LegacyPermissions::Delete.bit_u64(), 0b1000);
+
+    let roles = LegacyPermissions::from_bits(0b1010);
+    assert_eq!(
+        roles,
+        [LegacyPermissions::Write, LegacyPermissions::Delete]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(LegacyPermissions::set_to_bits_u64(&roles), 0b1010);
+    assert_eq!(LegacyPermissions::Delete as u8, 8);
+}
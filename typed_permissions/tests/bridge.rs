@@ -0,0 +1,60 @@
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::PermBridge;
+use type_permissions::PhantomToken;
+
+mod service_a {
+    #[derive(type_permissions::Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+    pub enum ServiceAPerm {
+        Read,
+        Write,
+        Internal,
+    }
+}
+
+mod service_b {
+    #[derive(type_permissions::Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+    pub enum ServiceBPerm {
+        Read,
+        Write,
+    }
+}
+
+use service_a::ServiceAPerm;
+use service_b::ServiceBPerm;
+
+impl PermBridge<ServiceBPerm> for ServiceAPerm {
+    fn bridge(&self) -> Option<ServiceBPerm> {
+        match self {
+            ServiceAPerm::Read => Some(ServiceBPerm::Read),
+            ServiceAPerm::Write => Some(ServiceBPerm::Write),
+            ServiceAPerm::Internal => None,
+        }
+    }
+}
+
+fn read_b<T: ?Sized + service_b::TRead>(_: PhantomToken<T>) {}
+
+#[test]
+fn tokens_bridge_to_equivalent_permissions() {
+    let roles = [ServiceAPerm::Read].iter().cloned().collect();
+    let token = service_a::Read::try_into_token(&roles).unwrap();
+
+    read_b(token.map::<_, service_b::Read, _>().unwrap());
+}
+
+#[test]
+fn bridging_only_grants_mapped_permissions() {
+    let roles = [ServiceAPerm::Read, ServiceAPerm::Internal]
+        .iter()
+        .cloned()
+        .collect();
+    let token =
+        And::<ServiceAPerm, service_a::Read, service_a::Internal>::try_into_token(&roles).unwrap();
+
+    assert!(token.map::<_, service_b::Read, _>().is_some());
+    assert!(token.map::<_, service_b::Write, _>().is_none());
+    assert!(token
+        .map::<_, And<ServiceBPerm, service_b::Read, service_b::Write>, _>()
+        .is_none());
+}
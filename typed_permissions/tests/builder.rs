@@ -0,0 +1,73 @@
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Permissions;
+use type_permissions::RoleSetBuilder;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Admin,
+}
+
+#[test]
+fn from_claims_parses_every_claim() {
+    let builder = RoleSetBuilder::<Permissions>::from_claims(vec!["Read", "Admin"]);
+    assert!(builder.unknown().is_empty());
+
+    let roles = builder.build();
+    assert_eq!(
+        roles,
+        [Permissions::Read, Permissions::Admin]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert!(And::<Permissions, Read, Admin>::try_into_token(&roles).is_some());
+    assert!(Write::try_into_token(&roles).is_none());
+}
+
+#[test]
+fn from_space_separated_matches_oauth_scopes() {
+    let roles =
+        RoleSetBuilder::<Permissions>::from_space_separated("  Read\tWrite  Admin ").build();
+    assert_eq!(roles.len(), 3);
+}
+
+#[test]
+fn unknown_scopes_are_reported_in_order() {
+    let builder = RoleSetBuilder::<Permissions>::from_space_separated("openid Read profile")
+        .claim("Write")
+        .claim("email");
+
+    assert_eq!(builder.unknown(), &["openid", "profile", "email"]);
+    assert_eq!(
+        builder.build(),
+        [Permissions::Read, Permissions::Write]
+            .iter()
+            .cloned()
+            .collect()
+    );
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Users")]
+enum UserPermissions {
+    #[perm(rename = "users:write")]
+    Write,
+    #[perm(rename = "users:read")]
+    Read,
+    Delete,
+}
+
+#[test]
+fn scopes_list_sorted_renamed_permissions() {
+    type Edit = And<UserPermissions, UsersWrite, And<UserPermissions, UsersDelete, UsersRead>>;
+
+    let scope = type_permissions::scopes::<Edit, _>();
+    assert_eq!(scope, "Delete users:read users:write");
+    assert_eq!(type_permissions::scopes::<UsersRead, _>(), "users:read");
+
+    let roles = RoleSetBuilder::<UserPermissions>::from_space_separated(&scope).build();
+    assert!(Edit::try_into_token(&roles).is_some());
+}
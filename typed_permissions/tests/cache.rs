@@ -0,0 +1,80 @@
+#![cfg(not(feature = "no_std"))]
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::collections::HashSet;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use type_permissions::And;
+use type_permissions::CachedDispatch;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::Permissions;
+
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+type Chain = And<Permissions, Read, And<Permissions, Write, Delete>>;
+
+fn allocations(f: impl Fn()) -> usize {
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    f();
+    ALLOCATIONS.load(Ordering::SeqCst) - before
+}
+
+// NOTE: This is the only test in this file as the allocation counter is shared by every test
+// running in the binary.
+#[test]
+fn cached_dispatch_only_allocates_once() {
+    let roles: HashSet<_> = Permissions::ALL.iter().cloned().collect();
+
+    assert_eq!(Chain::cached_dispatch(), &Chain::dispatch());
+    assert_eq!(Read::cached_dispatch(), &Read::dispatch());
+    assert!(std::ptr::eq(
+        Chain::cached_dispatch(),
+        Chain::cached_dispatch()
+    ));
+    assert!(std::ptr::eq(
+        Read::cached_dispatch(),
+        Read::cached_dispatch()
+    ));
+
+    let uncached = allocations(|| {
+        for _ in 0..100 {
+            assert!(roles.is_superset(&Chain::dispatch()));
+        }
+    });
+    let cached = allocations(|| {
+        for _ in 0..100 {
+            assert!(Chain::try_into_token_cached(&roles).is_some());
+            assert!(Or::<Permissions, Read, Write>::check_match_cached(&roles));
+        }
+    });
+
+    assert!(uncached >= 100);
+    assert_eq!(cached, 0);
+}
@@ -0,0 +1,347 @@
+use std::cell::Cell;
+
+use type_permissions::perms;
+use type_permissions::AllOf;
+use type_permissions::And;
+use type_permissions::AnyOf;
+use type_permissions::Branch;
+use type_permissions::Dispatch;
+use type_permissions::Either;
+use type_permissions::HashSet;
+use type_permissions::Not;
+use type_permissions::Nothing;
+use type_permissions::Or;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use type_permissions::TAnd;
+use type_permissions::Xor;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Guest,
+    Admin,
+    Buyer,
+    Seller,
+}
+
+fn roles(perms: &[Permissions]) -> HashSet<Permissions> {
+    perms.iter().cloned().collect()
+}
+
+#[test]
+fn not_admits_guests_only() {
+    let guest = roles(&[Permissions::Guest]);
+    let admin = roles(&[Permissions::Admin]);
+    let both = roles(&[Permissions::Guest, Permissions::Admin]);
+
+    assert!(Not::<Permissions, Admin>::try_into_token(&guest).is_some());
+    assert!(Not::<Permissions, Admin>::try_into_token(&HashSet::new()).is_some());
+    assert!(Not::<Permissions, Admin>::try_into_token(&admin).is_none());
+    assert!(Not::<Permissions, Admin>::try_into_token(&both).is_none());
+}
+
+#[test]
+fn not_dispatches_empty_set() {
+    assert!(Not::<Permissions, Admin>::dispatch().is_empty());
+}
+
+#[test]
+fn xor_requires_exactly_one_side() {
+    type BuyerXorSeller = Xor<Permissions, Buyer, Seller>;
+
+    assert!(BuyerXorSeller::try_into_token(&roles(&[])).is_none());
+    assert!(BuyerXorSeller::try_into_token(&roles(&[Permissions::Buyer])).is_some());
+    assert!(BuyerXorSeller::try_into_token(&roles(&[Permissions::Seller])).is_some());
+    assert!(
+        BuyerXorSeller::try_into_token(&roles(&[Permissions::Buyer, Permissions::Seller]))
+            .is_none()
+    );
+}
+
+#[test]
+fn xor_dispatches_both_sides() {
+    assert_eq!(
+        Xor::<Permissions, Buyer, Seller>::dispatch(),
+        roles(&[Permissions::Buyer, Permissions::Seller])
+    );
+}
+
+#[test]
+fn nothing_always_derives_a_token() {
+    assert!(Nothing::<Permissions>::dispatch().is_empty());
+    assert!(Nothing::<Permissions>::try_into_token(&roles(&[])).is_some());
+    assert!(Nothing::<Permissions>::try_into_token(&roles(&[Permissions::Admin])).is_some());
+}
+
+#[test]
+fn nothing_tokens_default_to_a_no_op_token() {
+    #[derive(Default)]
+    struct Public {
+        token: PhantomToken<Nothing<Permissions>>,
+    }
+
+    fn handle<T: ?Sized + Dispatch<Permissions>>(_: PhantomToken<T>) -> usize {
+        T::dispatch().len()
+    }
+
+    assert_eq!(handle(Public::default().token), 0);
+    assert_eq!(
+        PhantomToken::<Nothing<Permissions>>::default(),
+        Nothing::try_into_token(&roles(&[])).unwrap()
+    );
+}
+
+#[test]
+fn nothing_is_the_identity_of_and() {
+    type AdminAndNothing = And<Permissions, Admin, Nothing<Permissions>>;
+    type NothingAndAdmin = And<Permissions, Nothing<Permissions>, Admin>;
+
+    assert_eq!(AdminAndNothing::dispatch(), Admin::dispatch());
+    assert_eq!(NothingAndAdmin::dispatch(), Admin::dispatch());
+
+    for perms in [&[][..], &[Permissions::Guest], &[Permissions::Admin]].iter() {
+        let roles = roles(perms);
+        let expected = Admin::try_into_token(&roles).is_some();
+        assert_eq!(AdminAndNothing::try_into_token(&roles).is_some(), expected);
+        assert_eq!(NothingAndAdmin::try_into_token(&roles).is_some(), expected);
+    }
+}
+
+#[test]
+fn nothing_absorbs_or() {
+    assert!(Or::<Permissions, Admin, Nothing<Permissions>>::try_into_token(&roles(&[])).is_some());
+}
+
+#[test]
+fn matches_checks_single_permissions() {
+    assert!(type_permissions::matches::<Admin, _>(&roles(&[
+        Permissions::Admin
+    ])));
+    assert!(!type_permissions::matches::<Admin, _>(&roles(&[
+        Permissions::Guest
+    ])));
+}
+
+#[test]
+fn matches_checks_composites() {
+    type BuyerAndSeller = And<Permissions, Buyer, Seller>;
+    type BuyerOrSeller = Or<Permissions, Buyer, Seller>;
+
+    let both = roles(&[Permissions::Buyer, Permissions::Seller]);
+    let buyer = roles(&[Permissions::Buyer]);
+
+    assert!(type_permissions::matches::<BuyerAndSeller, _>(&both));
+    assert!(!type_permissions::matches::<BuyerAndSeller, _>(&buyer));
+    assert!(type_permissions::matches::<BuyerOrSeller, _>(&both));
+    assert!(!type_permissions::matches::<BuyerOrSeller, _>(&roles(&[])));
+}
+
+#[test]
+fn tuples_require_every_element() {
+    let seller = roles(&[Permissions::Guest, Permissions::Buyer, Permissions::Seller]);
+
+    assert!(<(Guest, Buyer)>::try_into_token(&seller).is_some());
+    assert!(<(Guest, Admin)>::try_into_token(&seller).is_none());
+    assert!(<(Guest, Buyer, Seller)>::try_into_token(&seller).is_some());
+    assert!(<(Guest, Buyer, Admin)>::try_into_token(&seller).is_none());
+    assert!(<(Guest, Buyer, Seller, Admin)>::try_into_token(&seller).is_none());
+    assert!(<(Guest, Buyer, Seller, Admin)>::try_into_token(&roles(Permissions::ALL)).is_some());
+}
+
+#[test]
+fn tuples_dispatch_the_union_of_their_elements() {
+    assert_eq!(
+        <(Guest, Admin)>::dispatch(),
+        roles(&[Permissions::Guest, Permissions::Admin])
+    );
+    assert_eq!(
+        <(Guest, Admin, Buyer)>::dispatch(),
+        And::<Permissions, Guest, And<Permissions, Admin, Buyer>>::dispatch()
+    );
+    assert_eq!(
+        <(Guest, Admin, Buyer, Seller)>::dispatch(),
+        roles(Permissions::ALL)
+    );
+}
+
+#[test]
+fn or_check_match_agrees_with_try_into_token() {
+    type BuyerOrSeller = Or<Permissions, Buyer, Seller>;
+
+    let buyer = roles(&[Permissions::Buyer]);
+    assert!(BuyerOrSeller::check_match(&buyer));
+    assert!(BuyerOrSeller::try_into_token(&buyer).is_some());
+    assert!(!BuyerOrSeller::check_match(&roles(&[Permissions::Guest])));
+
+    // Nested `Or`s are checked through `check_match` rather than `try_into_token`.
+    assert!(
+        And::<Permissions, Guest, BuyerOrSeller>::try_into_token(&roles(&[
+            Permissions::Guest,
+            Permissions::Seller
+        ]))
+        .is_some()
+    );
+}
+
+#[test]
+fn all_of_folds_into_and_chains() {
+    type Trade = AllOf<Permissions, (Guest, Buyer, Seller)>;
+    type Everyone = AllOf<Permissions, (Guest, Admin, Buyer, Seller, Nothing<Permissions>)>;
+
+    fn trade<T>(_: PhantomToken<T>)
+    where
+        T: ?Sized + TAnd<Permissions, Guest, And<Permissions, Buyer, Seller>>,
+    {
+    }
+
+    let seller = roles(&[Permissions::Guest, Permissions::Buyer, Permissions::Seller]);
+    let token: PhantomToken<perms!(Permissions; Guest, Buyer, Seller)> =
+        Trade::try_into_token(&seller).unwrap();
+    trade(token);
+
+    assert!(Everyone::try_into_token(&seller).is_none());
+    type Nested = perms!(Permissions; Guest, Admin, Buyer, Seller, Nothing<Permissions>);
+    let _: PhantomToken<Nested> = Everyone::try_into_token(&roles(Permissions::ALL)).unwrap();
+    assert_eq!(Everyone::dispatch(), roles(Permissions::ALL));
+}
+
+#[test]
+fn any_of_folds_into_or_chains() {
+    type Traders = AnyOf<Permissions, (Admin, Buyer, Seller)>;
+    type Anyone = AnyOf<Permissions, (Guest, Admin, Buyer, Seller, Not<Permissions, Guest>)>;
+
+    let buyer = roles(&[Permissions::Buyer]);
+    let guest = roles(&[Permissions::Guest]);
+
+    let _: PhantomToken<Or<Permissions, Admin, Or<Permissions, Buyer, Seller>>> =
+        Traders::try_into_token(&buyer).unwrap();
+    assert!(Traders::try_into_token(&guest).is_none());
+
+    assert!(Anyone::try_into_token(&buyer).is_some());
+    assert!(Anyone::try_into_token(&guest).is_some());
+    assert!(Anyone::try_into_token(&roles(&[])).is_some());
+}
+
+#[test]
+fn satisfied_branch_reports_the_matching_side() {
+    type BuyerOrSeller = Or<Permissions, Buyer, Seller>;
+
+    assert_eq!(
+        BuyerOrSeller::satisfied_branch(&roles(&[Permissions::Buyer])),
+        Some(Branch::Left)
+    );
+    assert_eq!(
+        BuyerOrSeller::satisfied_branch(&roles(&[Permissions::Seller])),
+        Some(Branch::Right)
+    );
+    assert_eq!(
+        BuyerOrSeller::satisfied_branch(&roles(&[Permissions::Buyer, Permissions::Seller])),
+        Some(Branch::Both)
+    );
+    assert_eq!(
+        BuyerOrSeller::satisfied_branch(&roles(&[Permissions::Admin])),
+        None
+    );
+}
+
+fn buy(_: PhantomToken<Buyer>) -> &'static str {
+    "bought"
+}
+
+fn sell(_: PhantomToken<Seller>) -> &'static str {
+    "sold"
+}
+
+fn trade(perms: &[Permissions]) -> Option<&'static str> {
+    match Or::<Permissions, Buyer, Seller>::satisfied_token(&roles(perms))? {
+        Either::Left(token) => Some(buy(token)),
+        Either::Right(token) => Some(sell(token)),
+    }
+}
+
+#[test]
+fn satisfied_token_dispatches_to_the_matching_side() {
+    assert_eq!(trade(&[Permissions::Buyer]), Some("bought"));
+    assert_eq!(trade(&[Permissions::Seller]), Some("sold"));
+    assert_eq!(
+        trade(&[Permissions::Buyer, Permissions::Seller]),
+        Some("bought")
+    );
+    assert_eq!(trade(&[Permissions::Guest]), None);
+}
+
+#[test]
+fn minimal_grant_picks_the_smallest_satisfied_branch() {
+    type Tree = Or<Permissions, And<Permissions, Buyer, Seller>, Or<Permissions, Admin, Guest>>;
+    type Both = And<Permissions, Or<Permissions, Admin, And<Permissions, Buyer, Seller>>, Guest>;
+
+    let all = roles(&[
+        Permissions::Guest,
+        Permissions::Admin,
+        Permissions::Buyer,
+        Permissions::Seller,
+    ]);
+    let grant = Tree::minimal_grant(&all).unwrap();
+    assert_eq!(grant.len(), 1);
+    assert!(Tree::check_match(&grant));
+
+    let traders = roles(&[Permissions::Buyer, Permissions::Seller]);
+    assert_eq!(Tree::minimal_grant(&traders), Some(traders.clone()));
+
+    assert_eq!(
+        Both::minimal_grant(&all),
+        Some(roles(&[Permissions::Admin, Permissions::Guest]))
+    );
+    assert_eq!(
+        Both::minimal_grant(&roles(&[
+            Permissions::Guest,
+            Permissions::Buyer,
+            Permissions::Seller
+        ])),
+        Some(roles(&[
+            Permissions::Guest,
+            Permissions::Buyer,
+            Permissions::Seller
+        ]))
+    );
+    assert_eq!(Both::minimal_grant(&traders), None);
+    assert_eq!(
+        Buyer::minimal_grant(&all),
+        Some(roles(&[Permissions::Buyer]))
+    );
+}
+
+thread_local! {
+    static BUILT: Cell<(bool, bool)> = const { Cell::new((false, false)) };
+}
+
+/// Stand-in for `Buyer` and `Seller` recording whether their dispatched set was built.
+struct Recorded<const RIGHT: bool>;
+
+impl<const RIGHT: bool> Dispatch<Permissions> for Recorded<RIGHT> {
+    fn dispatch() -> HashSet<Permissions> {
+        BUILT.with(|x| {
+            let (left, right) = x.get();
+            x.set((left || !RIGHT, right || RIGHT));
+        });
+
+        if RIGHT {
+            roles(&[Permissions::Seller])
+        } else {
+            roles(&[Permissions::Buyer])
+        }
+    }
+}
+
+#[test]
+fn or_short_circuits_on_the_first_satisfied_side() {
+    type Trade = Or<Permissions, Recorded<false>, Recorded<true>>;
+
+    BUILT.with(|x| x.set((false, false)));
+    assert!(Trade::try_into_token(&roles(&[Permissions::Buyer])).is_some());
+    assert_eq!(BUILT.with(Cell::get), (true, false));
+
+    BUILT.with(|x| x.set((false, false)));
+    assert!(Trade::try_into_token(&roles(&[Permissions::Seller])).is_some());
+    assert_eq!(BUILT.with(Cell::get), (true, true));
+}
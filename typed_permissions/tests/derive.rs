@@ -0,0 +1,591 @@
+use std::marker::PhantomData;
+
+use type_permissions::And;
+#[cfg(not(feature = "no_std"))]
+use type_permissions::CachedDispatch;
+use type_permissions::Dispatch;
+use type_permissions::DispatchBits;
+use type_permissions::HashSet;
+use type_permissions::ParsePermissionError;
+use type_permissions::PermissionBits;
+use type_permissions::PermissionMeta;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Billing")]
+enum BillingPermissions {
+    Read,
+    Write,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Can", iter)]
+enum Hierarchy {
+    #[implies(Write)]
+    Admin,
+    #[implies(Read)]
+    Write,
+    Read,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Users")]
+enum UserPermissions {
+    #[perm(rename = "can-read-users")]
+    Read,
+    Write,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Legacy")]
+enum LegacyPermissions {
+    #[perm(alias = "read", alias = "dashboard:view")]
+    CanViewDashboard,
+    #[perm(rename = "edit-dashboard", alias = "write")]
+    CanEditDashboard,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Admin", registry)]
+enum AdminPermissions {
+    #[perm(rename = "admin:users", doc = "Manage user accounts")]
+    Users,
+    #[perm(doc = "Read the audit log")]
+    Audit,
+    Billing,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Shop")]
+enum ShopPermissions {
+    #[perm(group = "billing")]
+    Invoice,
+    #[perm(group = "billing", group = "user-admin")]
+    Refund,
+    #[perm(group = "user-admin")]
+    Ban,
+    Browse,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(module = "report")]
+enum ReportPermissions {
+    #[implies(View)]
+    Export,
+    #[perm(group = "viewer")]
+    View,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Doc")]
+enum DocPermissions {
+    #[implies(Write)]
+    Admin,
+    #[perm(group = "editor")]
+    Write,
+    #[perm(group = "editor")]
+    Read,
+}
+
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+struct Billing;
+#[derive(Hash, Eq, PartialEq, Clone, Debug)]
+struct Shipping;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Tenant", iter)]
+enum TenantPermissions<B> {
+    Read(PhantomData<B>),
+    Write(PhantomData<B>),
+    Audit,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Gated", iter, registry)]
+enum GatedPermissions {
+    Read,
+    #[cfg(any())]
+    Preview,
+    #[cfg(feature = "serde")]
+    #[implies(Read)]
+    Export,
+    #[cfg_attr(feature = "serde", implies(Export))]
+    Admin,
+}
+
+#[test]
+fn all_lists_every_variant() {
+    assert_eq!(
+        Permissions::ALL,
+        &[Permissions::Read, Permissions::Write, Permissions::Delete]
+    );
+}
+
+#[test]
+fn indices_follow_declaration_order() {
+    assert_eq!(Permissions::PERMISSION_COUNT, 3);
+    assert_eq!(Permissions::Read.index(), 0);
+    assert_eq!(Permissions::Write.index(), 1);
+    assert_eq!(Permissions::Delete.index(), 2);
+
+    for (idx, perm) in Hierarchy::ALL.iter().enumerate() {
+        assert_eq!(perm.index(), idx);
+        assert_eq!(perm.bit(), 1 << idx);
+    }
+    assert_eq!(Hierarchy::PERMISSION_COUNT, Hierarchy::ALL.len());
+
+    let mut limits = [0u32; ShopPermissions::PERMISSION_COUNT];
+    limits[ShopPermissions::Refund.index()] = 5;
+    assert_eq!(limits, [0, 5, 0, 0]);
+}
+
+#[test]
+fn iter_yields_every_variant_in_declaration_order() {
+    assert_eq!(
+        Hierarchy::iter().collect::<Vec<_>>(),
+        [Hierarchy::Admin, Hierarchy::Write, Hierarchy::Read]
+    );
+    assert_eq!(
+        Hierarchy::iter().map(|x| x.index()).collect::<Vec<_>>(),
+        [0, 1, 2]
+    );
+    assert_eq!(
+        TenantPermissions::<Billing>::iter().collect::<Vec<_>>(),
+        TenantPermissions::<Billing>::ALL
+    );
+}
+
+#[test]
+fn consts_build_role_sets_without_naming_the_enum() {
+    let roles = HashSet::from([CAN_READ, CAN_WRITE]);
+    assert_eq!(
+        roles,
+        [Hierarchy::Read, Hierarchy::Write]
+            .iter()
+            .cloned()
+            .collect::<HashSet<_>>()
+    );
+    assert!(CanWrite::try_into_token(&roles).is_some());
+    assert!(CanAdmin::try_into_token(&roles).is_none());
+
+    assert_eq!([READ, DELETE].iter().collect::<HashSet<_>>().len(), 2);
+    assert_eq!(BILLING_WRITE, BillingPermissions::Write);
+}
+
+#[test]
+fn from_str_parses_variant_names() {
+    assert_eq!("Read".parse(), Ok(Permissions::Read));
+    assert_eq!("Delete".parse(), Ok(Permissions::Delete));
+}
+
+#[test]
+fn from_str_rejects_unknown_names() {
+    let err = "CanFly".parse::<Permissions>().unwrap_err();
+    assert_eq!(err, ParsePermissionError::new("CanFly"));
+    assert_eq!(err.name(), "CanFly");
+    assert_eq!(err.to_string(), "unknown permission `CanFly`");
+}
+
+#[test]
+fn as_str_matches_variant_names() {
+    assert_eq!(Permissions::Read.as_str(), "Read");
+    assert_eq!(Permissions::Write.as_str(), "Write");
+    assert_eq!(Permissions::Delete.to_string(), "Delete");
+}
+
+#[test]
+fn display_round_trips_through_from_str() {
+    for perm in Permissions::ALL {
+        assert_eq!(perm.to_string().parse::<Permissions>().as_ref(), Ok(perm));
+    }
+}
+
+#[test]
+fn markers_convert_into_their_variant() {
+    assert_eq!(Permissions::from(Write), Permissions::Write);
+    assert_eq!(
+        TenantPermissions::<Billing>::from(TenantRead),
+        TenantPermissions::Read(PhantomData)
+    );
+}
+
+#[test]
+fn has_traits_bound_on_permission_and_dispatch() {
+    fn read<T: ?Sized + HasRead>(_: PhantomToken<T>) -> HashSet<Permissions> {
+        T::dispatch()
+    }
+    fn tenant_read<B, T>(_: PhantomToken<T>) -> HashSet<TenantPermissions<B>>
+    where
+        B: std::hash::Hash + Eq,
+        T: ?Sized + HasTenantRead<B>,
+    {
+        T::dispatch()
+    }
+
+    let roles = [Permissions::Read].iter().cloned().collect();
+    assert_eq!(read(Read::try_into_token(&roles).unwrap()), roles);
+
+    let roles = [TenantPermissions::<Billing>::Read(PhantomData)]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(
+        tenant_read(TenantRead::try_into_token(&roles).unwrap()),
+        roles
+    );
+}
+
+#[test]
+fn group_tokens_require_every_member() {
+    fn billing<T: ?Sized + TShopBillingGroup>(_: PhantomToken<T>) {}
+    fn invoice<T: ?Sized + TShopInvoice>(_: PhantomToken<T>) {}
+
+    assert_eq!(
+        ShopBillingGroup::dispatch(),
+        [ShopPermissions::Invoice, ShopPermissions::Refund]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(
+        ShopUserAdminGroup::dispatch(),
+        [ShopPermissions::Refund, ShopPermissions::Ban]
+            .iter()
+            .cloned()
+            .collect()
+    );
+
+    let roles = [ShopPermissions::Invoice, ShopPermissions::Browse]
+        .iter()
+        .cloned()
+        .collect();
+    assert!(ShopBillingGroup::try_into_token(&roles).is_none());
+
+    let roles = [ShopPermissions::Invoice, ShopPermissions::Refund]
+        .iter()
+        .cloned()
+        .collect();
+    let token = ShopBillingGroup::try_into_token(&roles).unwrap();
+    billing(token);
+    invoice(token);
+    billing(
+        PhantomToken::<ShopAll>::try_new(&ShopPermissions::ALL.iter().cloned().collect()).unwrap(),
+    );
+}
+
+#[test]
+fn permission_sets_build_fluently_and_derive_tokens() {
+    let set = PermissionSet::new()
+        .insert_perm(Permissions::Read)
+        .union_with([Permissions::Write].iter().cloned().collect());
+
+    assert!(set.contains(&Permissions::Read));
+    assert!(!set.contains(&Permissions::Delete));
+    assert!(set.try_into::<And<Permissions, Read, Write>>().is_some());
+    assert!(set.try_into::<Delete>().is_none());
+    assert!(Write::try_into_token(&set).is_some());
+    assert_eq!(
+        set.into_inner(),
+        [Permissions::Read, Permissions::Write]
+            .iter()
+            .cloned()
+            .collect()
+    );
+
+    let tenant = TenantPermissionSet::default().insert_perm(TenantPermissions::<Billing>::Audit);
+    assert!(tenant.try_into::<TenantAudit>().is_some());
+}
+
+#[test]
+fn module_option_namespaces_generated_items() {
+    fn view<T: ?Sized + report::TView>(_: PhantomToken<T>) {}
+
+    let roles = [ReportPermissions::Export].iter().cloned().collect();
+    let token = report::Export::try_into_token(&roles).unwrap();
+    view(token);
+    view(token.weaken::<report::View, _>());
+
+    let roles = [ReportPermissions::View]
+        .iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+    assert!(report::Export::try_into_token(&roles).is_none());
+    assert!(report::ViewerGroup::try_into_token(&roles).is_some());
+    assert!(report::All::try_into_token(&roles).is_none());
+    assert_eq!(
+        ReportPermissions::from(report::View),
+        ReportPermissions::View
+    );
+    assert!(roles
+        .into_iter()
+        .collect::<report::PermissionSet>()
+        .try_into::<report::View>()
+        .is_some());
+}
+
+#[test]
+fn prefix_keeps_overlapping_variants_apart() {
+    fn billing_read<T: ?Sized + TBillingRead>(_: PhantomToken<T>) {}
+
+    assert!(Read::dispatch().contains(&Permissions::Read));
+    assert!(BillingRead::dispatch().contains(&BillingPermissions::Read));
+    assert!(BillingWrite::dispatch().contains(&BillingPermissions::Write));
+
+    let roles = [BillingPermissions::Read].iter().cloned().collect();
+    billing_read(PhantomToken::<BillingRead>::try_new(&roles).unwrap());
+}
+
+#[test]
+fn implied_permissions_satisfy_their_implications() {
+    let roles = [Hierarchy::Admin].iter().cloned().collect();
+
+    assert!(CanRead::try_into_token(&roles).is_some());
+    assert!(CanWrite::try_into_token(&roles).is_some());
+    assert!(And::<Hierarchy, CanRead, CanWrite>::try_into_token(&roles).is_some());
+    #[cfg(not(feature = "no_std"))]
+    assert!(CanRead::check_match_cached(&roles));
+    assert!(CanRead::check_bits(Hierarchy::set_to_bits(&roles)));
+}
+
+#[test]
+fn implications_only_flow_downwards() {
+    let roles = [Hierarchy::Write].iter().cloned().collect();
+
+    assert!(CanRead::try_into_token(&roles).is_some());
+    assert!(CanAdmin::try_into_token(&roles).is_none());
+    #[cfg(not(feature = "no_std"))]
+    assert!(!CanAdmin::check_match_cached(&roles));
+    assert!(!CanAdmin::check_bits(Hierarchy::set_to_bits(&roles)));
+    assert_eq!(
+        CanAdmin::dispatch(),
+        [Hierarchy::Admin].iter().cloned().collect()
+    );
+}
+
+#[test]
+fn implied_permissions_satisfy_trait_bounds() {
+    fn read<T: ?Sized + TCanRead>(_: PhantomToken<T>) {}
+
+    let roles = [Hierarchy::Admin].iter().cloned().collect();
+    let token = PhantomToken::<CanAdmin>::try_new(&roles).unwrap();
+    read(token);
+    read(token.weaken::<CanRead, _>());
+}
+
+mod audit {
+    use type_permissions::Dispatch;
+    use type_permissions::Permissions;
+
+    #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+    #[permissions(prefix = "Audit", visibility = "pub(super)")]
+    pub(super) enum AuditPermissions {
+        Read,
+    }
+
+    #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+    #[permissions(module = "scopes", visibility = "pub(super)")]
+    pub(super) enum ScopePermissions {
+        Read,
+    }
+
+    mod private {
+        use type_permissions::Permissions;
+
+        #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+        #[permissions(visibility = "")]
+        enum Secret {
+            Peek,
+        }
+
+        pub fn peek_dispatch_len() -> usize {
+            use type_permissions::Dispatch;
+            Peek::dispatch().len()
+        }
+    }
+
+    pub(super) fn private_markers_are_usable_in_their_module() -> usize {
+        AuditRead::dispatch().len() + private::peek_dispatch_len()
+    }
+}
+
+#[test]
+fn visibility_option_restricts_generated_items() {
+    fn audit_read<T: ?Sized + audit::TAuditRead>(_: PhantomToken<T>) {}
+
+    let roles = [audit::AuditPermissions::Read].iter().cloned().collect();
+    audit_read(PhantomToken::<audit::AuditRead>::try_new(&roles).unwrap());
+
+    let roles = [audit::ScopePermissions::Read].iter().cloned().collect();
+    assert!(audit::scopes::Read::try_into_token(&roles).is_some());
+    assert!(audit::scopes::PermissionSet::default().is_empty());
+    assert_eq!(audit::private_markers_are_usable_in_their_module(), 2);
+}
+
+#[test]
+fn rename_affects_parsing_but_not_type_names() {
+    assert_eq!("can-read-users".parse(), Ok(UserPermissions::Read));
+    assert!("Read".parse::<UserPermissions>().is_err());
+    assert_eq!(UserPermissions::Read.to_string(), "can-read-users");
+    assert_eq!(UserPermissions::Write.as_str(), "Write");
+
+    let roles = [UserPermissions::Read].iter().cloned().collect();
+    assert!(UsersRead::try_into_token(&roles).is_some());
+    assert_eq!(UsersRead::NAME, "UsersRead");
+}
+
+#[test]
+fn aliases_parse_into_the_same_variant() {
+    assert_eq!(
+        "CanViewDashboard".parse(),
+        Ok(LegacyPermissions::CanViewDashboard)
+    );
+    assert_eq!("read".parse(), Ok(LegacyPermissions::CanViewDashboard));
+    assert_eq!(
+        "dashboard:view".parse(),
+        Ok(LegacyPermissions::CanViewDashboard)
+    );
+    assert_eq!(
+        "edit-dashboard".parse(),
+        Ok(LegacyPermissions::CanEditDashboard)
+    );
+    assert_eq!("write".parse(), Ok(LegacyPermissions::CanEditDashboard));
+    assert!("CanEditDashboard".parse::<LegacyPermissions>().is_err());
+}
+
+#[test]
+fn aliases_are_never_displayed() {
+    assert_eq!(
+        LegacyPermissions::CanViewDashboard.to_string(),
+        "CanViewDashboard"
+    );
+    assert_eq!(
+        LegacyPermissions::CanEditDashboard.as_str(),
+        "edit-dashboard"
+    );
+
+    let roles = ["read", "write"]
+        .iter()
+        .map(|x| x.parse().unwrap())
+        .collect();
+    assert!(
+        And::<LegacyPermissions, LegacyCanViewDashboard, LegacyCanEditDashboard>::try_into_token(
+            &roles
+        )
+        .is_some()
+    );
+}
+
+#[test]
+fn registry_lists_permission_metadata() {
+    assert_eq!(
+        AdminPermissions::registry(),
+        &[
+            PermissionMeta::new("Users", "admin:users", "Manage user accounts"),
+            PermissionMeta::new("Audit", "Audit", "Read the audit log"),
+            PermissionMeta::new("Billing", "Billing", ""),
+        ]
+    );
+
+    let users = AdminPermissions::registry()[0];
+    assert_eq!(users.name(), "Users");
+    assert_eq!(users.scope(), AdminPermissions::Users.as_str());
+    assert_eq!(users.description(), "Manage user accounts");
+}
+
+#[test]
+fn generic_enums_dispatch_per_parameter() {
+    let roles = [TenantPermissions::<Billing>::Read(PhantomData)]
+        .iter()
+        .cloned()
+        .collect();
+
+    assert!(TenantRead::try_into_token(&roles).is_some());
+    assert!(TenantWrite::try_into_token(&roles).is_none());
+    #[cfg(not(feature = "no_std"))]
+    assert!(TenantRead::check_match_cached(&roles));
+    assert!(
+        <TenantRead as DispatchBits<TenantPermissions<Billing>>>::check_bits(
+            TenantPermissions::set_to_bits(&roles)
+        )
+    );
+
+    let shipping: HashSet<TenantPermissions<Shipping>> = TenantRead::dispatch();
+    assert_eq!(shipping.len(), 1);
+    assert!(shipping.contains(&TenantPermissions::Read(PhantomData)));
+}
+
+#[test]
+fn generic_enums_parse_and_list_variants() {
+    assert_eq!(
+        "Write".parse(),
+        Ok(TenantPermissions::<Billing>::Write(PhantomData))
+    );
+    assert_eq!(TenantPermissions::<Billing>::Audit.to_string(), "Audit");
+    assert_eq!(TenantPermissions::<Shipping>::ALL.len(), 3);
+}
+
+#[test]
+fn cfg_gated_variants_follow_the_enabled_features() {
+    let enabled = if cfg!(feature = "serde") { 3 } else { 2 };
+    assert_eq!(GatedPermissions::ALL.len(), enabled);
+    assert_eq!(GatedPermissions::PERMISSION_COUNT, enabled);
+    assert_eq!(GatedPermissions::iter().count(), enabled);
+    assert_eq!(GatedPermissions::registry().len(), enabled);
+    assert_eq!(GatedAll::dispatch().len(), enabled);
+    assert!("Preview".parse::<GatedPermissions>().is_err());
+
+    let roles = [GatedPermissions::Admin].iter().cloned().collect();
+    assert!(GatedAdmin::try_into_token(&roles).is_some());
+    assert_eq!(
+        GatedRead::try_into_token(&roles).is_some(),
+        cfg!(feature = "serde")
+    );
+
+    #[cfg(feature = "serde")]
+    {
+        assert!(GatedExport::try_into_token(&roles).is_some());
+        assert_eq!("Export".parse(), Ok(GatedPermissions::Export));
+    }
+    #[cfg(not(feature = "serde"))]
+    assert!("Export".parse::<GatedPermissions>().is_err());
+}
+
+#[test]
+fn groups_and_all_honor_implications() {
+    type Editor = And<DocPermissions, DocWrite, DocRead>;
+    const ROLES: u128 = 0b101;
+
+    let roles = [DocPermissions::Admin, DocPermissions::Read]
+        .iter()
+        .cloned()
+        .collect();
+    assert!(Editor::check_match(&roles));
+    assert!(DocEditorGroup::check_match(&roles));
+    #[cfg(not(feature = "no_std"))]
+    assert!(DocEditorGroup::check_match_cached(&roles));
+    assert!(DocEditorGroup::check_bits(DocPermissions::set_to_bits(
+        &roles
+    )));
+    assert!(type_permissions::const_check::<DocEditorGroup, ROLES>());
+    assert!(DocAll::check_match(&roles));
+    #[cfg(not(feature = "no_std"))]
+    assert!(DocAll::check_match_cached(&roles));
+    assert!(DocAll::check_bits(ROLES));
+    assert!(type_permissions::const_check::<DocAll, ROLES>());
+
+    let roles = [DocPermissions::Admin].iter().cloned().collect();
+    assert!(!DocEditorGroup::check_match(&roles));
+    #[cfg(not(feature = "no_std"))]
+    assert!(!DocEditorGroup::check_match_cached(&roles));
+    assert!(!DocEditorGroup::check_bits(0b001));
+    assert!(!type_permissions::const_check::<DocAll, 0b001>());
+    assert!(DocAll::try_into_token(&roles).is_none());
+}
@@ -0,0 +1,214 @@
+use std::convert::TryInto;
+use std::marker::PhantomData;
+
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::PermissionError;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use type_permissions::PhantomTokenOptionExt;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Ord, PartialOrd, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+#[test]
+fn required_sorted_is_stable() {
+    type Chain = And<Permissions, Delete, And<Permissions, Read, Write>>;
+
+    let expected = vec![Permissions::Read, Permissions::Write, Permissions::Delete];
+    for _ in 0..16 {
+        assert_eq!(Chain::required_sorted(), expected);
+    }
+    assert_eq!(Delete::required_sorted(), vec![Permissions::Delete]);
+}
+
+#[test]
+#[cfg(not(feature = "no_std"))]
+fn dispatch_ref_is_stable_across_calls() {
+    type Chain = And<Permissions, Delete, And<Permissions, Read, Write>>;
+
+    assert_eq!(Chain::dispatch_ref(), &Chain::dispatch());
+    assert!(std::ptr::eq(Chain::dispatch_ref(), Chain::dispatch_ref()));
+    assert!(std::ptr::eq(Read::dispatch_ref(), Read::dispatch_ref()));
+    assert!(!std::ptr::eq(Read::dispatch_ref(), Write::dispatch_ref()));
+
+    let roles = Permissions::ALL.iter().cloned().collect::<HashSet<_>>();
+    assert!(roles.is_superset(Chain::dispatch_ref()));
+}
+
+#[test]
+fn try_into_token_verbose_reports_missing_permissions() {
+    type Chain = And<Permissions, Delete, And<Permissions, Read, Write>>;
+
+    let roles = [Permissions::Read].iter().cloned().collect();
+    let err = Chain::try_into_token_verbose(&roles).unwrap_err();
+    assert_eq!(
+        err.missing(),
+        &[Permissions::Write, Permissions::Delete]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(err.to_string(), "missing permissions: Delete, Write");
+
+    let roles = Permissions::ALL.iter().cloned().collect();
+    assert!(Chain::try_into_token_verbose(&roles).is_ok());
+}
+
+fn delete_all(roles: &HashSet<Permissions>) -> Result<&'static str, PermissionError<Permissions>> {
+    let _token = And::<Permissions, Read, Delete>::require(roles)?;
+    Ok("deleted")
+}
+
+#[test]
+fn require_works_with_the_question_mark_operator() {
+    let roles = [Permissions::Read, Permissions::Delete]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(delete_all(&roles), Ok("deleted"));
+}
+
+fn write(roles: &HashSet<Permissions>) -> Result<&'static str, PermissionError<Permissions>> {
+    let token = Write::try_into_token(roles).into_result()?;
+    let _token = token.refresh(roles).into_result()?;
+    Ok("written")
+}
+
+#[test]
+fn into_result_works_with_the_question_mark_operator() {
+    let roles = [Permissions::Write].iter().cloned().collect();
+    assert_eq!(write(&roles), Ok("written"));
+
+    let roles = [Permissions::Read].iter().cloned().collect();
+    let required = [Permissions::Write].iter().cloned().collect::<HashSet<_>>();
+    assert_eq!(
+        write(&roles),
+        Err(PermissionError::Denied {
+            required: required.clone(),
+            missing: required,
+        })
+    );
+}
+
+#[test]
+fn require_reports_required_and_missing_permissions() {
+    let roles = [Permissions::Read].iter().cloned().collect();
+    let err = delete_all(&roles).unwrap_err();
+
+    let PermissionError::Denied { required, missing } = &err;
+    assert_eq!(
+        required,
+        &[Permissions::Read, Permissions::Delete]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(missing, &[Permissions::Delete].iter().cloned().collect());
+    assert_eq!(err.to_string(), "permission denied, missing: Delete");
+}
+
+fn required<T: Dispatch<Permissions>>(_: T) -> HashSet<Permissions> {
+    T::dispatch()
+}
+
+#[test]
+fn references_and_boxes_dispatch_like_their_target() {
+    let expected = [Permissions::Read].iter().cloned().collect::<HashSet<_>>();
+    assert_eq!(required(&Read), expected);
+    assert_eq!(required(Box::new(Read)), expected);
+    assert_eq!(
+        <Box<dyn TRead> as Dispatch<Permissions>>::dispatch(),
+        expected
+    );
+
+    let roles = [Permissions::Read].iter().cloned().collect();
+    assert!(<&Read>::try_into_token(&roles).is_some());
+    assert!(<&And<Permissions, Read, Write>>::try_into_token(&roles).is_none());
+}
+
+#[test]
+fn phantom_data_dispatches_like_its_target() {
+    let expected = [Permissions::Read].iter().cloned().collect::<HashSet<_>>();
+    assert_eq!(required(PhantomData::<Read>), expected);
+    assert_eq!(
+        <PhantomData<dyn TRead> as Dispatch<Permissions>>::dispatch(),
+        expected
+    );
+
+    let roles = [Permissions::Read].iter().cloned().collect();
+    assert!(<PhantomData<Read>>::try_into_token(&roles).is_some());
+    assert!(<PhantomData<Write>>::try_into_token(&roles).is_none());
+}
+
+#[test]
+fn try_into_token_from_iter_accepts_vecs_and_slices() {
+    type ReadWrite = And<Permissions, Read, Write>;
+
+    let roles = vec![Permissions::Write, Permissions::Read];
+    assert!(ReadWrite::try_into_token_from_iter(&roles).is_some());
+    assert!(ReadWrite::try_into_token_from_iter(&roles[..1]).is_none());
+    assert!(ReadWrite::try_into_token_from_iter(roles).is_some());
+    assert!(Delete::try_into_token_from_iter(Vec::<Permissions>::new()).is_none());
+}
+
+#[test]
+fn explain_reports_granted_and_missing_permissions() {
+    type Chain = And<Permissions, Delete, And<Permissions, Read, Write>>;
+
+    let roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect();
+    assert_eq!(
+        Chain::explain(&roles),
+        "granted: Read, Write; missing: Delete"
+    );
+    assert_eq!(
+        Chain::explain(&HashSet::new()),
+        "granted: none; missing: Delete, Read, Write"
+    );
+    assert_eq!(Read::explain(&roles), "granted: Read; missing: none");
+}
+
+#[test]
+fn required_iter_yields_the_dispatch_set() {
+    type Chain = And<Permissions, Delete, And<Permissions, Read, Write>>;
+
+    let mut required = type_permissions::required_iter::<Chain, _>().collect::<Vec<_>>();
+    required.sort();
+    assert_eq!(required, Chain::required_sorted());
+    assert_eq!(
+        type_permissions::required_iter::<Read, _>().collect::<Vec<_>>(),
+        [Permissions::Read]
+    );
+}
+
+#[test]
+fn role_sets_convert_into_tokens() {
+    let roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    let token: Result<PhantomToken<And<Permissions, Read, Write>>, _> = (&roles).try_into();
+    assert!(token.is_ok());
+
+    let err =
+        TryInto::<PhantomToken<And<Permissions, Read, Delete>>>::try_into(&roles).unwrap_err();
+    assert_eq!(
+        err,
+        PermissionError::Denied {
+            required: [Permissions::Read, Permissions::Delete]
+                .iter()
+                .cloned()
+                .collect(),
+            missing: [Permissions::Delete].iter().cloned().collect(),
+        }
+    );
+}
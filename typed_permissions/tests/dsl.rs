@@ -0,0 +1,84 @@
+use type_permissions::permissions;
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+
+permissions! {
+    Read,
+    Write implies Read,
+    Admin implies Write,
+}
+
+mod shop {
+    use type_permissions::permissions;
+
+    permissions! {
+        #[permissions(prefix = "Shop")]
+        pub enum ShopPermissions {
+            #[perm(rename = "invoice:create")]
+            Invoice in billing,
+            Refund implies Invoice in billing + support,
+            Ban in support,
+        }
+    }
+}
+
+mod gated {
+    use type_permissions::permissions;
+
+    permissions! {
+        #[permissions(prefix = "Gated")]
+        pub enum GatedPermissions {
+            View,
+            #[cfg(feature = "serde")]
+            Export implies View,
+            #[cfg(any())]
+            Launch implies View,
+            Manage implies Export + Launch,
+        }
+    }
+}
+
+#[test]
+fn implications_are_applied() {
+    let roles = [Permissions::Admin].iter().cloned().collect();
+    assert!(Read::try_into_token(&roles).is_some());
+    assert!(Write::try_into_token(&roles).is_some());
+
+    let roles = [Permissions::Write].iter().cloned().collect();
+    assert!(Read::try_into_token(&roles).is_some());
+    assert!(Admin::try_into_token(&roles).is_none());
+}
+
+#[test]
+fn headers_carry_attributes_and_groups() {
+    fn billing<T: ?Sized + shop::TShopBillingGroup>(_: PhantomToken<T>) {}
+
+    assert_eq!(shop::ShopPermissions::Invoice.to_string(), "invoice:create");
+
+    let roles = [shop::ShopPermissions::Refund].iter().cloned().collect();
+    assert!(shop::ShopInvoice::try_into_token(&roles).is_some());
+    assert!(shop::ShopSupportGroup::try_into_token(&roles).is_none());
+
+    let roles = [
+        shop::ShopPermissions::Refund,
+        shop::ShopPermissions::Invoice,
+    ]
+    .iter()
+    .cloned()
+    .collect();
+    billing(shop::ShopBillingGroup::try_into_token(&roles).unwrap());
+}
+
+#[test]
+fn implications_of_cfg_gated_variants_are_gated_too() {
+    let roles = [gated::GatedPermissions::Manage].iter().cloned().collect();
+    assert!(gated::GatedManage::try_into_token(&roles).is_some());
+    assert_eq!(
+        gated::GatedView::try_into_token(&roles).is_some(),
+        cfg!(feature = "serde")
+    );
+    assert_eq!(
+        gated::GatedPermissions::ALL.len(),
+        if cfg!(feature = "serde") { 3 } else { 2 }
+    );
+}
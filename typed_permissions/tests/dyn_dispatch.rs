@@ -0,0 +1,64 @@
+use std::marker::PhantomData;
+
+use type_permissions::And;
+use type_permissions::DynDispatch;
+use type_permissions::HashSet;
+use type_permissions::Not;
+use type_permissions::Or;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+fn roles(perms: &[Permissions]) -> HashSet<Permissions> {
+    perms.iter().cloned().collect()
+}
+
+fn checks() -> Vec<Box<dyn DynDispatch<Permissions>>> {
+    vec![
+        Box::new(Read),
+        Box::new(PhantomData::<And<Permissions, Read, Write>>),
+        Box::new(PhantomData::<Or<Permissions, Write, Delete>>),
+        Box::new(PhantomData::<Not<Permissions, Delete>>),
+    ]
+}
+
+#[test]
+fn mixed_checkers_can_be_stored_together() {
+    let allowed = |perms: &[Permissions]| {
+        let roles = roles(perms);
+        checks()
+            .iter()
+            .map(|x| x.dyn_check(&roles))
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(allowed(&[Permissions::Read]), [true, false, false, true]);
+    assert_eq!(
+        allowed(&[Permissions::Read, Permissions::Write]),
+        [true, true, true, true]
+    );
+    assert_eq!(allowed(&[Permissions::Delete]), [false, false, true, false]);
+}
+
+#[test]
+fn dyn_dispatch_returns_the_dispatched_set() {
+    let checks = checks();
+    assert_eq!(checks[0].dyn_dispatch(), roles(&[Permissions::Read]));
+    assert_eq!(
+        checks[1].dyn_dispatch(),
+        roles(&[Permissions::Read, Permissions::Write])
+    );
+}
+
+#[test]
+fn unsized_permission_types_are_checked_through_a_reference() {
+    let check: &dyn TRead = &Read;
+    assert!(check.dyn_check(&roles(&[Permissions::Read])));
+    assert!(!check.dyn_check(&roles(&[Permissions::Write])));
+    assert_eq!(check.dyn_dispatch(), roles(&[Permissions::Read]));
+}
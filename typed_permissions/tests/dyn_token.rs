@@ -0,0 +1,57 @@
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::DynToken;
+use type_permissions::HashSet;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    #[implies(Write)]
+    Admin,
+    Read,
+    Write,
+}
+
+fn roles(perms: &[Permissions]) -> HashSet<Permissions> {
+    perms.iter().cloned().collect()
+}
+
+fn write<T: ?Sized + TWrite>(_: PhantomToken<T>) {}
+
+#[test]
+fn erased_tokens_round_trip() {
+    let token = Write::try_into_token(&roles(&[Permissions::Write])).unwrap();
+    let erased = token.erase();
+
+    assert_eq!(erased.roles(), &roles(&[Permissions::Write]));
+    write(erased.try_into::<Write>().unwrap());
+    assert!(erased.try_into::<Read>().is_none());
+}
+
+#[test]
+fn erased_tokens_only_carry_proven_permissions() {
+    let granted = roles(&[Permissions::Read, Permissions::Write]);
+    let token = Read::try_into_token(&granted).unwrap();
+
+    assert!(token.erase().try_into::<Write>().is_none());
+}
+
+#[test]
+fn erased_tokens_are_storable_together() {
+    let granted = roles(&[Permissions::Admin, Permissions::Read]);
+    let tokens: Vec<DynToken<Permissions>> = vec![
+        Read::try_into_token(&granted).unwrap().erase(),
+        Admin::try_into_token(&granted).unwrap().erase(),
+        And::<Permissions, Read, Admin>::try_into_token(&granted)
+            .unwrap()
+            .erase(),
+    ];
+
+    let writers = tokens.iter().filter_map(|x| x.try_into::<Write>()).count();
+    assert_eq!(writers, 2);
+
+    assert!(tokens[2]
+        .try_into::<And<Permissions, Read, Write>>()
+        .is_some());
+}
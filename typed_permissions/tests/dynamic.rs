@@ -0,0 +1,49 @@
+use type_permissions::DynamicRequirement;
+use type_permissions::HashSet;
+use type_permissions::Permissions;
+use type_permissions::RequirementMode;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+fn roles(perms: &[Permissions]) -> HashSet<Permissions> {
+    perms.iter().cloned().collect()
+}
+
+#[test]
+fn all_requires_every_permission() {
+    let requirement = DynamicRequirement::all(vec![Permissions::Read, Permissions::Write]);
+
+    assert_eq!(requirement.mode(), RequirementMode::All);
+    assert!(requirement.check(&roles(&[Permissions::Read, Permissions::Write])));
+    assert!(requirement.check(&roles(Permissions::ALL)));
+    assert!(!requirement.check(&roles(&[Permissions::Read])));
+    assert!(!requirement
+        .with(Permissions::Delete)
+        .check(&roles(&[Permissions::Read, Permissions::Write])));
+}
+
+#[test]
+fn any_requires_one_permission() {
+    let requirement = DynamicRequirement::new(
+        roles(&[Permissions::Write, Permissions::Delete]),
+        RequirementMode::Any,
+    );
+
+    assert!(requirement.check(&roles(&[Permissions::Delete])));
+    assert!(requirement.check(&roles(&[Permissions::Read, Permissions::Write])));
+    assert!(!requirement.check(&roles(&[Permissions::Read])));
+    assert!(!requirement.check(&HashSet::new()));
+}
+
+#[test]
+fn empty_requirements_follow_and_and_or() {
+    let none = Vec::<Permissions>::new();
+
+    assert!(DynamicRequirement::all(none.clone()).check(&HashSet::new()));
+    assert!(!DynamicRequirement::any(none).check(&roles(Permissions::ALL)));
+}
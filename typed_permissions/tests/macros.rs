@@ -0,0 +1,95 @@
+use type_permissions::any;
+use type_permissions::perms;
+use type_permissions::which_allowed;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::Or;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use type_permissions::TAnd;
+use type_permissions::TOr;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+fn acquire<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn five<T>(_: PhantomToken<T>)
+where
+    T: ?Sized + TAnd<Permissions, A, perms!(Permissions; B, C, D, E)>,
+{
+}
+
+#[test]
+fn perms_builds_nested_and_chains() {
+    type Manual =
+        And<Permissions, A, And<Permissions, B, And<Permissions, C, And<Permissions, D, E>>>>;
+
+    five(acquire::<perms!(Permissions; A, B, C, D, E)>());
+    five(acquire::<Manual>());
+    assert_eq!(
+        <perms!(Permissions; A, B, C, D, E)>::dispatch(),
+        Permissions::ALL.iter().cloned().collect()
+    );
+}
+
+#[test]
+fn perms_with_one_permission_is_that_permission() {
+    let token: PhantomToken<A> = acquire::<perms!(Permissions; A)>();
+    assert_eq!(token.permissions(), A::dispatch());
+}
+
+fn staff<T>(_: PhantomToken<T>)
+where
+    T: ?Sized + TOr<Permissions, A, any!(Permissions; B, C)>,
+{
+}
+
+#[test]
+fn any_builds_nested_or_chains() {
+    type Manual = Or<Permissions, A, Or<Permissions, B, C>>;
+
+    staff(acquire::<any!(Permissions; A, B, C)>());
+    staff(acquire::<Manual>());
+}
+
+#[test]
+fn any_derives_tokens_from_any_role() {
+    type Staff = any!(Permissions; A, B, C);
+
+    let roles = |perms: &[Permissions]| perms.iter().cloned().collect::<HashSet<_>>();
+
+    assert!(Staff::try_into_token(&roles(&[Permissions::A])).is_some());
+    assert!(Staff::try_into_token(&roles(Permissions::ALL)).is_some());
+    assert!(Staff::try_into_token(&roles(&[Permissions::D])).is_none());
+    assert!(Staff::try_into_token(&roles(&[])).is_none());
+}
+
+#[test]
+fn any_with_one_permission_is_that_permission() {
+    let _: PhantomToken<A> = acquire::<any!(Permissions; A)>();
+}
+
+#[test]
+fn which_allowed_checks_every_type_against_the_role_set() {
+    let roles = [Permissions::A, Permissions::B]
+        .iter()
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    let allowed = which_allowed!(
+        roles,
+        [A, C, perms!(Permissions; A, B), any!(Permissions; C, B)]
+    );
+    assert_eq!(allowed, vec![true, false, true, true]);
+    assert_eq!(which_allowed!(&roles, [D, E]), vec![false, false]);
+}
@@ -0,0 +1,64 @@
+use type_permissions::And;
+use type_permissions::AndMixed;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::Or;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(module = "db")]
+enum DbPerm {
+    Select,
+    Insert,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(module = "api")]
+enum ApiPerm {
+    Invoke,
+    Manage,
+}
+
+fn db(perms: &[DbPerm]) -> HashSet<DbPerm> {
+    perms.iter().cloned().collect()
+}
+
+fn api(perms: &[ApiPerm]) -> HashSet<ApiPerm> {
+    perms.iter().cloned().collect()
+}
+
+type Ingest = AndMixed<And<DbPerm, db::Select, db::Insert>, Or<ApiPerm, api::Invoke, api::Manage>>;
+
+#[test]
+fn and_mixed_requires_both_domains() {
+    assert!(Ingest::check_match((
+        &db(&[DbPerm::Select, DbPerm::Insert]),
+        &api(&[ApiPerm::Invoke])
+    )));
+    assert!(!Ingest::check_match((
+        &db(&[DbPerm::Select]),
+        &api(&[ApiPerm::Invoke, ApiPerm::Manage])
+    )));
+    assert!(!Ingest::check_match((
+        &db(&[DbPerm::Select, DbPerm::Insert]),
+        &api(&[])
+    )));
+}
+
+#[test]
+fn and_mixed_tokens_split_and_combine() {
+    let token = Ingest::try_into_token((
+        &db(&[DbPerm::Select, DbPerm::Insert]),
+        &api(&[ApiPerm::Manage]),
+    ))
+    .unwrap();
+    let (db_token, _api_token) = token.split();
+    let (_select, _insert) = db_token.split();
+
+    let select = db::Select::try_into_token(&db(&[DbPerm::Select])).unwrap();
+    let invoke = api::Invoke::try_into_token(&api(&[ApiPerm::Invoke])).unwrap();
+    let _: PhantomToken<AndMixed<db::Select, api::Invoke>> = select.combine_mixed(invoke);
+
+    assert!(Ingest::try_into_token((&db(&[DbPerm::Insert]), &api(&[ApiPerm::Invoke]))).is_none());
+}
@@ -0,0 +1,58 @@
+use type_permissions::perms;
+use type_permissions::And;
+use type_permissions::Not;
+use type_permissions::Nothing;
+use type_permissions::Or;
+use type_permissions::PermName;
+use type_permissions::Permissions;
+use type_permissions::Xor;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Billing")]
+enum BillingPermissions {
+    Refund,
+}
+
+#[test]
+fn leaves_are_named_after_their_struct() {
+    const READ: &str = Read::NAME;
+
+    assert_eq!(READ, "Read");
+    assert_eq!(Write::name(), "Write");
+    assert_eq!(<dyn TDelete>::name(), "Delete");
+    assert_eq!(All::NAME, "All");
+    assert_eq!(BillingRefund::name(), "BillingRefund");
+    assert_eq!(BillingAll::name(), "BillingAll");
+}
+
+#[test]
+fn combinators_build_composite_names() {
+    assert_eq!(And::<Permissions, Read, Write>::name(), "Read & Write");
+    assert_eq!(Or::<Permissions, Read, Write>::name(), "Read | Write");
+    assert_eq!(Xor::<Permissions, Read, Write>::name(), "Read ^ Write");
+    assert_eq!(Not::<Permissions, Delete>::name(), "!Delete");
+    assert_eq!(Nothing::<Permissions>::name(), "Nothing");
+}
+
+#[test]
+fn nested_composite_names_are_parenthesized() {
+    assert_eq!(
+        <perms!(Permissions; Read, Write, Delete)>::name(),
+        "Read & (Write & Delete)"
+    );
+    assert_eq!(
+        Or::<Permissions, And<Permissions, Read, Write>, Not<Permissions, Delete>>::name(),
+        "(Read & Write) | !Delete"
+    );
+    assert_eq!(
+        Not::<Permissions, Or<Permissions, Read, Write>>::name(),
+        "!(Read | Write)"
+    );
+}
@@ -0,0 +1,148 @@
+use type_permissions::requires;
+use type_permissions::requires_any;
+use type_permissions::requires_impl;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::PermissionError;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+struct Context {
+    roles: HashSet<Permissions>,
+}
+
+struct Service {
+    ctx: Context,
+}
+
+impl Service {
+    fn new(roles: &[Permissions]) -> Self {
+        Self {
+            ctx: Context {
+                roles: roles.iter().cloned().collect(),
+            },
+        }
+    }
+
+    #[requires(from = "self.ctx.roles", perm = "Write")]
+    fn write(&self, value: &str) -> Result<usize, PermissionError<Permissions>> {
+        Ok(value.len())
+    }
+
+    #[requires(from = "self.ctx.roles", perm = "Write", perm = "Delete")]
+    fn purge(&self) -> Result<(), PermissionError<Permissions>> {
+        Ok(())
+    }
+
+    #[requires_any(from = "self.ctx.roles", perm = "Write", perm = "Delete")]
+    fn modify(&self) -> Result<(), PermissionError<Permissions>> {
+        Ok(())
+    }
+}
+
+struct Store {
+    values: Vec<String>,
+}
+
+#[requires_impl("Read")]
+impl Store {
+    fn open() -> Self {
+        Self {
+            values: vec!["a".into(), "b".into()],
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[requires("Write")]
+    fn push<S: Into<String>>(&mut self, value: S) {
+        self.values.push(value.into());
+    }
+}
+
+trait Audit {
+    fn entries(&self) -> Result<usize, PermissionError<Permissions>>;
+}
+
+#[requires_impl(from = "self.ctx.roles", perm = "Read")]
+impl Audit for Service {
+    fn entries(&self) -> Result<usize, PermissionError<Permissions>> {
+        Ok(self.ctx.roles.len())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum AppError {
+    Forbidden,
+}
+
+impl From<PermissionError<Permissions>> for AppError {
+    fn from(_: PermissionError<Permissions>) -> Self {
+        Self::Forbidden
+    }
+}
+
+#[requires(from = "roles", perm = "Read")]
+async fn read(roles: &HashSet<Permissions>, id: u32) -> Result<u32, AppError> {
+    Ok(id)
+}
+
+#[test]
+fn guards_allow_calls_with_the_permissions() {
+    let service = Service::new(&[Permissions::Write, Permissions::Delete]);
+
+    assert_eq!(service.write("abc"), Ok(3));
+    assert_eq!(service.purge(), Ok(()));
+    assert_eq!(service.modify(), Ok(()));
+}
+
+#[test]
+fn guards_deny_calls_without_the_permissions() {
+    let service = Service::new(&[Permissions::Read]);
+
+    assert_eq!(
+        service.write("abc"),
+        Err(PermissionError::Denied {
+            required: [Permissions::Write].iter().cloned().collect(),
+            missing: [Permissions::Write].iter().cloned().collect(),
+        })
+    );
+    assert!(Service::new(&[Permissions::Write]).purge().is_err());
+    assert!(service.modify().is_err());
+}
+
+#[tokio::test]
+async fn guards_convert_into_the_error_type() {
+    let roles = [Permissions::Read].iter().cloned().collect();
+
+    assert_eq!(read(&roles, 7).await, Ok(7));
+    assert_eq!(read(&HashSet::new(), 7).await, Err(AppError::Forbidden));
+}
+
+#[test]
+fn requires_impl_adds_a_token_to_every_method() {
+    let roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect();
+    let read = Read::try_into_token(&roles).unwrap();
+    let write = Write::try_into_token(&roles).unwrap();
+
+    let mut store = Store::open(read);
+    store.push(write, read, "c");
+    assert_eq!(store.len(read), 3);
+}
+
+#[test]
+fn requires_impl_guards_trait_impls() {
+    assert_eq!(Service::new(&[Permissions::Read]).entries(), Ok(1));
+    assert!(Service::new(&[Permissions::Write]).entries().is_err());
+}
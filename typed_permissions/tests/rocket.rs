@@ -0,0 +1,71 @@
+#![cfg(feature = "rocket")]
+
+use std::collections::HashSet;
+
+use rocket::get;
+use rocket::http::Header;
+use rocket::http::Status;
+use rocket::local::blocking::Client;
+use rocket::routes;
+use type_permissions::rocket::PermissionFairing;
+use type_permissions::rocket::TypedPerm;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanReadUsers,
+    CanDeleteUsers,
+}
+
+fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) -> &'static str {
+    "deleted"
+}
+
+#[get("/admin")]
+fn admin(perm: TypedPerm<Permissions, CanDeleteUsers>) -> &'static str {
+    delete_users(perm.into_inner())
+}
+
+fn client(header: &'static str) -> Client {
+    let fairing = PermissionFairing::new(|value: &str| {
+        value
+            .split(',')
+            .map(|x| x.parse().ok())
+            .collect::<Option<HashSet<Permissions>>>()
+    })
+    .header(header);
+
+    Client::tracked(rocket::build().attach(fairing).mount("/", routes![admin])).unwrap()
+}
+
+#[test]
+fn typed_perm_admits_matching_roles() {
+    let client = client("x-roles");
+    let response = client
+        .get("/admin")
+        .header(Header::new("x-roles", "CanReadUsers,CanDeleteUsers"))
+        .dispatch();
+
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_string().as_deref(), Some("deleted"));
+}
+
+#[test]
+fn typed_perm_rejects_missing_roles() {
+    let client = client("x-roles");
+
+    let response = client
+        .get("/admin")
+        .header(Header::new("x-roles", "CanReadUsers"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let response = client
+        .get("/admin")
+        .header(Header::new("x-roles", "CanFly"))
+        .dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    assert_eq!(client.get("/admin").dispatch().status(), Status::Forbidden);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+use serde::Deserialize;
+use serde::Serialize;
+use type_permissions::serde::deserialize_token;
+use type_permissions::serde::serialize_token;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+#[test]
+fn permissions_serialize_to_variant_names() {
+    let perms = vec![Permissions::CanRead, Permissions::CanWrite];
+    assert_eq!(
+        serde_json::to_string(&perms).unwrap(),
+        r#"["CanRead","CanWrite"]"#
+    );
+}
+
+#[test]
+fn permissions_round_trip() {
+    let perms = vec![Permissions::CanWrite, Permissions::CanRead];
+    let json = serde_json::to_string(&perms).unwrap();
+    assert_eq!(
+        serde_json::from_str::<Vec<Permissions>>(&json).unwrap(),
+        perms
+    );
+}
+
+#[test]
+fn unknown_permission_fails_to_deserialize() {
+    let err = serde_json::from_str::<Vec<Permissions>>(r#"["CanRead","CanFly"]"#).unwrap_err();
+    assert!(err.to_string().contains("unknown variant `CanFly`"));
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Users")]
+enum UserPermissions {
+    #[perm(rename = "can-read-users")]
+    Read,
+    Write,
+}
+
+#[test]
+fn renamed_permissions_use_their_new_name() {
+    let perms = vec![UserPermissions::Read, UserPermissions::Write];
+    let json = serde_json::to_string(&perms).unwrap();
+
+    assert_eq!(json, r#"["can-read-users","Write"]"#);
+    assert_eq!(
+        serde_json::from_str::<Vec<UserPermissions>>(&json).unwrap(),
+        perms
+    );
+    assert!(serde_json::from_str::<UserPermissions>(r#""Read""#).is_err());
+}
+
+#[derive(Serialize, Deserialize)]
+struct Session {
+    #[serde(
+        serialize_with = "serialize_token::<Permissions, And<Permissions, CanRead, CanWrite>, _>",
+        deserialize_with = "deserialize_token::<Permissions, And<Permissions, CanRead, CanWrite>, _>"
+    )]
+    token: PhantomToken<And<Permissions, CanRead, CanWrite>>,
+}
+
+#[test]
+fn tokens_round_trip_through_their_dispatch_set() {
+    let roles = [Permissions::CanRead, Permissions::CanWrite]
+        .iter()
+        .cloned()
+        .collect();
+    let session = Session {
+        token: And::try_into_token(&roles).unwrap(),
+    };
+
+    let json = serde_json::to_value(&session).unwrap();
+    let mut serialized = serde_json::from_value::<Vec<Permissions>>(json["token"].clone()).unwrap();
+    serialized.sort_by_key(|x| x.bit());
+    assert_eq!(serialized, [Permissions::CanRead, Permissions::CanWrite]);
+
+    assert!(serde_json::from_value::<Session>(json).is_ok());
+}
+
+#[test]
+fn token_deserialization_reverifies_permissions() {
+    let err = serde_json::from_str::<Session>(r#"{"token":["CanRead"]}"#)
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("dont satisfy the token type"));
+    assert!(serde_json::from_str::<Session>(r#"{"token":["CanFly"]}"#).is_err());
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "small")]
+
+use std::alloc::GlobalAlloc;
+use std::alloc::Layout;
+use std::alloc::System;
+use std::cell::Cell;
+
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::Permissions;
+
+/// Counts the allocations of the current thread only, as the test harness allocates on its own
+/// threads concurrently.
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|x| x.set(x.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+fn allocations(f: impl FnOnce() -> bool) -> (bool, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn single_permission_checks_dont_allocate() {
+    let roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect();
+
+    assert_eq!(
+        allocations(|| Read::try_into_token(&roles).is_some()),
+        (true, 0)
+    );
+    assert_eq!(
+        allocations(|| Delete::try_into_token(&roles).is_some()),
+        (false, 0)
+    );
+    assert_eq!(allocations(|| All::check_match(&roles)), (false, 0));
+    assert_eq!(
+        allocations(|| {
+            And::<Permissions, Read, Or<Permissions, Write, Delete>>::check_match(&roles)
+        }),
+        (true, 0)
+    );
+}
+
+#[test]
+fn dispatch_still_returns_a_hash_set() {
+    assert_eq!(All::dispatch(), Permissions::ALL.iter().cloned().collect());
+}
@@ -0,0 +1,277 @@
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use type_permissions::TAnd;
+use type_permissions::TOr;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+    Delete,
+}
+
+fn acquire<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn read_write<T: ?Sized + TAnd<Permissions, Read, Write>>(_: PhantomToken<T>) {}
+
+fn read_write_delete<T>(_: PhantomToken<T>)
+where
+    T: ?Sized + TAnd<Permissions, Read, And<Permissions, Write, Delete>>,
+{
+}
+
+fn read_or_write<T: ?Sized + TOr<Permissions, Read, Write>>(_: PhantomToken<T>) {}
+
+#[test]
+fn commute_swaps_and_operands() {
+    let token = acquire::<And<Permissions, Write, Read>>();
+    read_write(token.commute());
+}
+
+#[test]
+fn associate_regroups_and_chains() {
+    let token = acquire::<And<Permissions, And<Permissions, Read, Write>, Delete>>();
+    read_write_delete(token.associate_right());
+
+    let token = acquire::<And<Permissions, Read, And<Permissions, Write, Delete>>>();
+    read_write_delete(token.associate_left().associate_right());
+}
+
+#[test]
+fn commute_swaps_or_operands() {
+    let token = acquire::<Or<Permissions, Write, Read>>();
+    read_or_write(token.commute());
+}
+
+fn read<T: ?Sized + TRead>(_: PhantomToken<T>) {}
+fn write<T: ?Sized + TWrite>(_: PhantomToken<T>) {}
+fn delete<T: ?Sized + TDelete>(_: PhantomToken<T>) {}
+
+#[test]
+fn weaken_drops_permissions_from_and() {
+    read(acquire::<And<Permissions, Read, Write>>().weaken::<Read, _>());
+    read(acquire::<And<Permissions, Write, Read>>().weaken::<Read, _>());
+    read(acquire::<Read>().weaken::<Read, _>());
+
+    let token = acquire::<And<Permissions, Read, And<Permissions, Write, Delete>>>();
+    delete(token.weaken::<Delete, _>());
+
+    let token = acquire::<And<Permissions, Read, And<Permissions, Write, Delete>>>();
+    let token = token.weaken::<And<Permissions, Write, Delete>, _>();
+    delete(token.weaken::<Delete, _>());
+}
+
+#[test]
+fn combine_merges_tokens_into_and() {
+    read_write(acquire::<Read>().combine(acquire::<Write>()));
+
+    let token = acquire::<Read>().combine(acquire::<Write>().combine(acquire::<Delete>()));
+    read_write_delete(token);
+}
+
+#[test]
+fn split_decomposes_and_tokens() {
+    let (read_token, write_token) = acquire::<And<Permissions, Read, Write>>().split();
+    read(read_token);
+    write(write_token);
+
+    let (read_token, rest) =
+        acquire::<And<Permissions, Read, And<Permissions, Write, Delete>>>().split();
+    read(read_token);
+    let (write_token, delete_token) = rest.split();
+    write(write_token);
+    delete(delete_token);
+}
+
+#[test]
+fn all_satisfies_single_permission_bounds() {
+    read(acquire::<All>());
+    delete(acquire::<All>());
+    read(acquire::<All>().weaken::<Read, _>());
+}
+
+#[test]
+fn all_requires_the_full_role_set() {
+    let mut roles = type_permissions::HashSet::new();
+    roles.insert(Permissions::Read);
+    roles.insert(Permissions::Write);
+    assert!(All::try_into_token(&roles).is_none());
+
+    roles.insert(Permissions::Delete);
+    assert!(All::try_into_token(&roles).is_some());
+    assert_eq!(All::dispatch(), roles);
+}
+
+#[test]
+fn tokens_are_copy() {
+    let token = acquire::<And<Permissions, Read, Write>>();
+    read_write(token);
+    read_write(token);
+    read(token.weaken::<Read, _>());
+}
+
+#[test]
+fn debug_prints_the_permission_type() {
+    let debug = format!("{:?}", acquire::<Read>());
+    assert!(debug.starts_with("PhantomToken<"));
+    assert!(debug.ends_with("::Read>"));
+}
+
+#[test]
+fn tokens_compare_by_permissions() {
+    let read_write = acquire::<And<Permissions, Read, Write>>();
+    let write_read = acquire::<And<Permissions, Write, Read>>();
+
+    assert_eq!(read_write, read_write);
+    assert!(read_write.same_permissions(&write_read));
+    assert!(!read_write.same_permissions(&acquire::<Read>()));
+    assert_eq!(read_write.permissions(), write_read.permissions());
+    assert_eq!(
+        acquire::<Read>().permissions(),
+        [Permissions::Read].iter().cloned().collect()
+    );
+}
+
+#[test]
+fn required_lists_the_permissions_of_the_token_type() {
+    fn proves<T: ?Sized + Dispatch<Permissions>>(_: PhantomToken<T>) -> usize {
+        PhantomToken::<T>::required().len()
+    }
+
+    assert_eq!(
+        PhantomToken::<And<Permissions, Read, Write>>::required(),
+        [Permissions::Read, Permissions::Write]
+            .iter()
+            .cloned()
+            .collect()
+    );
+    assert_eq!(proves(acquire::<And<Permissions, Read, Delete>>()), 2);
+}
+
+#[test]
+fn tokens_can_key_hash_maps() {
+    let mut setup = std::collections::HashMap::new();
+    setup.insert(acquire::<And<Permissions, Read, Write>>(), "read write");
+    setup.insert(
+        acquire::<And<Permissions, Read, Write>>(),
+        "read write again",
+    );
+    assert_eq!(setup.len(), 1);
+    assert_eq!(
+        setup.get(&acquire::<And<Permissions, Read, Write>>()),
+        Some(&"read write again")
+    );
+
+    let mut users = type_permissions::HashSet::new();
+    users.insert(acquire::<dyn TRead>().attach(7u64));
+    users.insert(acquire::<dyn TRead>().attach(8u64));
+    users.insert(acquire::<dyn TRead>().attach(7u64));
+    assert_eq!(users.len(), 2);
+}
+
+#[test]
+fn assert_valid_accepts_matching_roles() {
+    let roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect();
+    acquire::<And<Permissions, Read, Write>>().assert_valid(&roles);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "token doesnt match the role set")]
+fn assert_valid_panics_on_bogus_tokens() {
+    let roles = [Permissions::Read].iter().cloned().collect();
+    acquire::<Delete>().assert_valid(&roles);
+}
+
+#[test]
+fn cast_reorders_and_chains() {
+    let token = acquire::<And<Permissions, Delete, And<Permissions, Write, Read>>>();
+    read_write_delete(unsafe {
+        token.cast::<_, And<Permissions, Read, And<Permissions, Write, Delete>>>()
+    });
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "token cast between types dispatching different permissions")]
+fn cast_panics_on_different_permissions() {
+    let token = acquire::<And<Permissions, Read, Write>>();
+    read_write_delete(unsafe {
+        token.cast::<_, And<Permissions, Read, And<Permissions, Write, Delete>>>()
+    });
+}
+
+#[test]
+fn refresh_keeps_tokens_whose_roles_are_retained() {
+    let token = acquire::<And<Permissions, Read, Write>>();
+    let mut roles = [Permissions::Read, Permissions::Write]
+        .iter()
+        .cloned()
+        .collect::<type_permissions::HashSet<_>>();
+
+    let token = token.refresh(&roles).unwrap();
+    roles.insert(Permissions::Delete);
+    read_write(token.refresh(&roles).unwrap());
+}
+
+#[test]
+fn refresh_drops_tokens_whose_roles_are_revoked() {
+    let token = acquire::<And<Permissions, Read, Write>>();
+    let roles = [Permissions::Read].iter().cloned().collect();
+
+    assert!(token.refresh(&roles).is_none());
+    assert!(acquire::<Read>().refresh(&roles).is_some());
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct UserId(u64);
+
+#[test]
+fn attached_claims_travel_with_the_token() {
+    fn read_as<T: ?Sized + TRead>(token: PhantomToken<T, UserId>) -> u64 {
+        token.claims().0
+    }
+
+    let token = acquire::<And<Permissions, Read, Write>>().attach(UserId(7));
+    assert_eq!(token.claims(), &UserId(7));
+    read_write(token.detach().0);
+
+    let (token, user) = token.detach();
+    assert_eq!(read_as(token.weaken::<Read, _>().attach(user)), 7);
+}
+
+#[test]
+fn attached_claims_are_compared_and_printed() {
+    let token = acquire::<Read>().attach(UserId(7));
+
+    assert_eq!(token, acquire::<Read>().attach(UserId(7)));
+    assert_ne!(token, acquire::<Read>().attach(UserId(8)));
+    assert!(format!("{:?}", token).ends_with("::Read>(UserId(7))"));
+    assert_eq!(std::mem::size_of::<PhantomToken<Read>>(), 0);
+}
+
+#[test]
+fn tokens_are_always_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<PhantomToken<dyn TRead>>();
+    assert_send_sync::<PhantomToken<And<Permissions, Read, dyn TWrite>>>();
+}
+
+#[tokio::test]
+async fn tokens_can_be_held_across_await_points() {
+    let token = acquire::<dyn TRead>();
+    let handle = tokio::spawn(async move {
+        tokio::task::yield_now().await;
+        read(token);
+    });
+    handle.await.unwrap();
+}
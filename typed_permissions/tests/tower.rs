@@ -0,0 +1,54 @@
+#![cfg(feature = "tower")]
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+
+use http::Request;
+use tower::service_fn;
+use tower::ServiceBuilder;
+use tower::ServiceExt;
+use type_permissions::tower::PermissionLayer;
+use type_permissions::Dispatch;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanReadUsers,
+    CanDeleteUsers,
+}
+
+fn decode(req: &Request<()>) -> HashSet<Permissions> {
+    req.headers()
+        .get_all("x-permission")
+        .iter()
+        .filter_map(|x| x.to_str().ok()?.parse().ok())
+        .collect()
+}
+
+async fn call(permissions: &[&str]) -> bool {
+    let service = ServiceBuilder::new()
+        .layer(PermissionLayer::new(decode))
+        .service(service_fn(|req: Request<()>| async move {
+            let roles = req.extensions().get::<HashSet<Permissions>>().unwrap();
+            Ok::<_, Infallible>(CanDeleteUsers::try_into_token(roles).is_some())
+        }));
+
+    let mut req = Request::builder();
+    for perm in permissions {
+        req = req.header("x-permission", *perm);
+    }
+
+    service.oneshot(req.body(()).unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn layer_inserts_decoded_roles() {
+    assert!(call(&["CanDeleteUsers"]).await);
+    assert!(call(&["CanReadUsers", "CanDeleteUsers"]).await);
+}
+
+#[tokio::test]
+async fn layer_inserts_roles_for_unprivileged_requests() {
+    assert!(!call(&[]).await);
+    assert!(!call(&["CanReadUsers"]).await);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "tracing")]
+
+use std::collections::HashSet;
+
+use tracing_test::traced_test;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    Read,
+    Write,
+}
+
+#[test]
+#[traced_test]
+fn derived_tokens_emit_events() {
+    let roles = [Permissions::Read].iter().cloned().collect::<HashSet<_>>();
+
+    assert!(Read::try_into_token(&roles).is_some());
+    assert!(logs_contain("derived token"));
+    assert!(logs_contain("granted=true"));
+
+    assert!(And::<Permissions, Read, Write>::require(&roles).is_err());
+    assert!(logs_contain("granted=false"));
+    assert!(logs_contain("And<"));
+}
+
+#[test]
+#[traced_test]
+fn matches_emits_events() {
+    assert!(!type_permissions::matches::<Write, _>(&HashSet::new()));
+    assert!(logs_contain("checked permissions"));
+    assert!(logs_contain("granted=false"));
+}
@@ -0,0 +1,11 @@
+//! Compile tests documenting the guarantees of the crate: `tests/ui/pass` must build while every
+//! case in `tests/ui/fail` must be rejected with the checked in `.stderr`, for example a token
+//! for the wrong permission, an `And` missing an operand or an `Or` token passed where an `And` is
+//! required. Run with `TRYBUILD=overwrite` to regenerate the `.stderr` files after intended
+//! diagnostic changes.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/ui/pass/*.rs");
+    t.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,24 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+use type_permissions::TAnd;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanCallFunctionX,
+    CanCallFunctionY,
+}
+
+fn function_xy<T>(_: PhantomToken<T>)
+where
+    T: ?Sized + TAnd<Permissions, CanCallFunctionX, CanCallFunctionY>,
+{
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    function_xy(token::<CanCallFunctionX>());
+}
@@ -0,0 +1,17 @@
+use type_permissions::And;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use type_permissions::TAnd;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+// Without a generic order the bound only accepts the operands in the listed order.
+fn read_write<T: ?Sized + TAnd<Permissions, CanRead, CanWrite>>(_: PhantomToken<T>) {}
+
+fn main() {
+    read_write(unsafe { PhantomToken::<And<Permissions, CanWrite, CanRead>>::new_unchecked() });
+}
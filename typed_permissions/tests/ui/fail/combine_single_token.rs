@@ -0,0 +1,21 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+use type_permissions::TAnd;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn read_write<T: ?Sized + TAnd<Permissions, CanRead, CanWrite>>(_: PhantomToken<T>) {}
+
+fn main() {
+    read_write(token::<CanRead>());
+    read_write(token::<CanWrite>());
+}
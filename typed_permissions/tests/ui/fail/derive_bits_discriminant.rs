@@ -0,0 +1,17 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(bits)]
+enum Permissions {
+    Read = 1,
+    Write = 3,
+}
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(bits)]
+enum Clashing {
+    Read = 2,
+    Write,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    Read,
+    TRead,
+}
+
+fn main() {}
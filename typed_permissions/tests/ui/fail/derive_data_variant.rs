@@ -0,0 +1,13 @@
+use type_permissions::Permissions;
+
+#[derive(Hash, Eq, PartialEq, Clone)]
+pub struct TenantId(u32);
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanAccessTenant(TenantId),
+    CanAccessUser { id: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    CanReadAll,
+    #[perm(alias = "CanReadAll")]
+    CanRead,
+}
+
+fn main() {}
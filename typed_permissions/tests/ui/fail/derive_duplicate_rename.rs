@@ -0,0 +1,11 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    #[perm(rename = "read")]
+    CanRead,
+    #[perm(rename = "read")]
+    CanReadAll,
+}
+
+fn main() {}
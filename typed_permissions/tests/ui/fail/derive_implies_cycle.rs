@@ -0,0 +1,11 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    #[implies(CanWrite)]
+    CanRead,
+    #[implies(CanRead)]
+    CanWrite,
+}
+
+fn main() {}
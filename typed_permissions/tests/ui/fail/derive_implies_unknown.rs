@@ -0,0 +1,9 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    #[implies(CanFly)]
+    CanRead,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    #[perm(group = "1st-tier")]
+    CanRead,
+}
+
+fn main() {}
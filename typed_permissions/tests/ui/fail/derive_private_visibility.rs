@@ -0,0 +1,13 @@
+mod permissions {
+    use type_permissions::Permissions;
+
+    #[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+    #[permissions(visibility = "")]
+    pub enum Permissions {
+        CanRead,
+    }
+}
+
+fn main() {
+    let _ = permissions::CanRead;
+}
@@ -0,0 +1,8 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+pub struct Permissions {
+    can_read: bool,
+}
+
+fn main() {}
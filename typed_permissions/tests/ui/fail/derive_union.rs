@@ -0,0 +1,8 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+pub union Permissions {
+    can_read: u8,
+}
+
+fn main() {}
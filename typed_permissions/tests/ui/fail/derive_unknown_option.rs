@@ -0,0 +1,9 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+#[permissions(prefx = "App")]
+enum Permissions {
+    CanRead,
+}
+
+fn main() {}
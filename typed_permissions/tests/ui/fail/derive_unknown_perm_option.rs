@@ -0,0 +1,9 @@
+use type_permissions::Permissions;
+
+#[derive(Permissions)]
+enum Permissions {
+    #[perm(description = "Read things")]
+    CanRead,
+}
+
+fn main() {}
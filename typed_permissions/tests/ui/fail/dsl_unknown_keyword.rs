@@ -0,0 +1,8 @@
+use type_permissions::permissions;
+
+permissions! {
+    CanRead,
+    CanWrite requires CanRead,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use type_permissions::Or;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+fn main() {
+    // An `Or` token doesnt prove both permissions, thus it can never be erased.
+    let token = unsafe { PhantomToken::<Or<Permissions, CanRead, CanWrite>>::new_unchecked() };
+    let _ = token.erase();
+}
@@ -0,0 +1,19 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanCallFunctionX,
+    CanCallFunctionY,
+}
+
+fn function_y<T: ?Sized + TCanCallFunctionY>(_: PhantomToken<T>) {}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    function_y(token::<CanCallFunctionX>());
+}
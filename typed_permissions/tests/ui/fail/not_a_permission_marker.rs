@@ -0,0 +1,17 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+}
+
+// Not generated by the derive, thus not a permission marker.
+struct CanWrite;
+
+fn read<T: ?Sized + Dispatch<Permissions>>(_: PhantomToken<T>) {}
+
+fn main() {
+    read(unsafe { PhantomToken::<CanWrite>::new_unchecked() });
+}
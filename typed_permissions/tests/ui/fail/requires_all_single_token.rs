@@ -0,0 +1,28 @@
+use type_permissions::requires_all;
+use type_permissions::requires_any;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+#[requires_any("CanRead", "CanWrite")]
+fn view() {}
+
+#[requires_all("CanRead", "CanWrite")]
+fn edit() {}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    view(token::<Or<Permissions, CanRead, CanWrite>>());
+    edit(token::<Or<Permissions, CanRead, CanWrite>>());
+    edit(token::<CanRead>());
+}
@@ -0,0 +1,22 @@
+use type_permissions::requires_impl;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+}
+
+trait Store {
+    fn len(&self) -> usize;
+}
+
+struct Memory;
+
+#[requires_impl("CanRead")]
+impl Store for Memory {
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use type_permissions::requires;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+}
+
+#[requires("CanRead")]
+fn read() {}
+
+fn main() {
+    read();
+}
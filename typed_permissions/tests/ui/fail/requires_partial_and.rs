@@ -0,0 +1,23 @@
+use type_permissions::requires;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+    CanDelete,
+}
+
+#[requires("CanRead", "CanWrite", "CanDelete")]
+fn purge() {}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    purge(token::<And<Permissions, CanRead, CanWrite>>());
+}
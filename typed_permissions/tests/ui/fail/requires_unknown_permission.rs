@@ -0,0 +1,16 @@
+use type_permissions::requires;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+#[requires(enum = Permissions, "CanWirte")]
+fn write() {}
+
+#[requires(enum = Permissions, "CanRead", "Delete")]
+fn delete() {}
+
+fn main() {}
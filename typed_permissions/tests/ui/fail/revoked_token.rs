@@ -0,0 +1,17 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+}
+
+fn read<T: ?Sized + TCanRead>(_: PhantomToken<T>) {}
+
+fn main() {
+    let roles = [Permissions::CanRead].iter().cloned().collect();
+    let token = CanRead::try_into_token(&roles).unwrap();
+    read(token);
+    read(token.revoke());
+}
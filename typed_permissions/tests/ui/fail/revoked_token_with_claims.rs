@@ -0,0 +1,22 @@
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+}
+
+struct UserId(u64);
+
+fn read<T: ?Sized + TCanRead>(token: PhantomToken<T, UserId>) -> u64 {
+    token.claims().0
+}
+
+fn main() {
+    let roles = [Permissions::CanRead].iter().cloned().collect();
+    let token = CanRead::try_into_token(&roles).unwrap().attach(UserId(7));
+    let revoked = token.revoke();
+    assert_eq!(revoked.claims().0, 7);
+    read(revoked);
+}
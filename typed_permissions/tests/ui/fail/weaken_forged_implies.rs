@@ -0,0 +1,22 @@
+use type_permissions::implies::Here;
+use type_permissions::Dispatch;
+use type_permissions::Implies;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanDeleteUsers,
+}
+
+impl type_permissions::__private::ImpliesSealed<CanDeleteUsers, Here> for CanRead {}
+impl Implies<CanDeleteUsers, Here> for CanRead {}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    let _ = token::<CanRead>().weaken::<CanDeleteUsers, _>();
+}
@@ -0,0 +1,18 @@
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    let _ = token::<Or<Permissions, CanRead, CanWrite>>().weaken::<CanRead, _>();
+}
@@ -0,0 +1,50 @@
+//! Everything generated by the derive is documented and allows the lints it could trip, thus
+//! crates denying `missing_docs`, warnings or clippy lints can derive `Permissions` on a public
+//! enum.
+#![deny(missing_docs, warnings, clippy::all)]
+
+use type_permissions::Permissions;
+
+/// Permissions of the api.
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(registry, bits)]
+pub enum ApiPermissions {
+    /// Write access, implies read access.
+    #[implies(Read)]
+    #[perm(group = "editor", alias = "write")]
+    Write,
+    /// Read access.
+    #[perm(group = "editor")]
+    Read,
+}
+
+/// Permissions of the reports, wrapped in a module.
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(module = "report")]
+pub enum ReportPermissions {
+    /// Export access.
+    Export,
+}
+
+/// Generic permissions.
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(prefix = "Tenant")]
+pub enum TenantPermissions<B> {
+    /// Read access.
+    Read(core::marker::PhantomData<B>),
+}
+
+/// Scopes named after the ones of an identity provider.
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+#[permissions(module = "scopes", iter)]
+#[allow(non_camel_case_types)]
+pub enum Scopes {
+    /// Read only access.
+    #[perm(group = "viewer")]
+    read_only,
+    /// Access to the audit log.
+    #[perm(group = "viewer")]
+    audit_log,
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use type_permissions::requires;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+    CanDelete,
+}
+
+#[requires("CanRead")]
+fn read(id: u32) -> u32 {
+    id
+}
+
+#[requires("CanRead", "CanWrite", "CanDelete")]
+fn purge() {}
+
+struct Service;
+
+impl Service {
+    #[requires("CanWrite")]
+    fn write(&self, value: &str) -> usize {
+        value.len()
+    }
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    assert_eq!(read(token::<CanRead>(), 3), 3);
+    purge(token::<And<Permissions, CanRead, And<Permissions, CanWrite, CanDelete>>>());
+    assert_eq!(Service.write(token::<CanWrite>(), "abc"), 3);
+}
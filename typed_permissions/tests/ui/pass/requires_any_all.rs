@@ -0,0 +1,33 @@
+use type_permissions::requires_all;
+use type_permissions::requires_any;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+    CanDelete,
+}
+
+#[requires_any("CanRead", "CanWrite")]
+fn view() {}
+
+#[requires_any("CanRead", "CanWrite", "CanDelete")]
+fn touch() {}
+
+#[requires_all("CanRead", "CanWrite")]
+fn edit() {}
+
+fn main() {
+    let roles = [Permissions::CanRead].iter().cloned().collect();
+    view(Or::<Permissions, CanRead, CanWrite>::try_into_token(&roles).unwrap());
+    view(Or::<Permissions, CanWrite, CanRead>::try_into_token(&roles).unwrap());
+    touch(Or::<Permissions, CanRead, Or<Permissions, CanWrite, CanDelete>>::try_into_token(&roles).unwrap());
+    assert!(And::<Permissions, CanRead, CanWrite>::try_into_token(&roles).is_none());
+
+    let roles = [Permissions::CanRead, Permissions::CanWrite].iter().cloned().collect();
+    edit(And::<Permissions, CanRead, CanWrite>::try_into_token(&roles).unwrap());
+}
@@ -0,0 +1,73 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::pin::pin;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+
+use type_permissions::requires;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+struct Service<'a> {
+    prefix: &'a str,
+}
+
+impl<'a> Service<'a> {
+    #[requires("CanRead")]
+    async fn read<'b, V: Display, const N: usize>(&'b self, values: [V; N]) -> String
+    where
+        'a: 'b,
+    {
+        let values = values.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        format!("{}{}", self.prefix, values.join(","))
+    }
+
+    #[requires("CanRead", "CanWrite")]
+    async fn copy<V>(&mut self, value: V) -> V
+    where
+        V: Clone,
+    {
+        value.clone()
+    }
+}
+
+#[requires("CanWrite")]
+async fn write<T: AsRef<str>>(value: T) -> usize {
+    value.as_ref().len()
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(x) = future.as_mut().poll(&mut cx) {
+            return x;
+        }
+    }
+}
+
+fn main() {
+    let mut service = Service { prefix: "ids:" };
+    assert_eq!(
+        block_on(service.read(token::<CanRead>(), [1, 2, 3])),
+        "ids:1,2,3"
+    );
+    assert_eq!(
+        block_on(service.copy(token::<And<Permissions, CanRead, CanWrite>>(), 7)),
+        7
+    );
+    assert_eq!(block_on(write(token::<CanWrite>(), "abc")), 3);
+}
@@ -0,0 +1,52 @@
+use type_permissions::requires;
+use type_permissions::requires_any;
+use type_permissions::Dispatch;
+use type_permissions::HashSet;
+use type_permissions::PermissionError;
+use type_permissions::PhantomToken;
+
+mod perms {
+    use type_permissions::Permissions;
+
+    #[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+    #[permissions(prefix = "Can", visibility = "pub(crate)")]
+    pub(crate) enum Permissions {
+        #[perm(group = "editor")]
+        Read,
+        #[perm(group = "editor")]
+        Write,
+    }
+}
+
+use perms::*;
+
+#[requires(enum = perms::Permissions, "CanRead", "CanWrite")]
+fn edit() {}
+
+#[requires_any(enum = perms::Permissions, "CanEditorGroup", "CanAll")]
+fn publish() {}
+
+struct Service {
+    roles: HashSet<Permissions>,
+}
+
+impl Service {
+    #[requires(enum = Permissions, from = "self.roles", perm = "CanWrite")]
+    fn write(&self) -> Result<(), PermissionError<Permissions>> {
+        Ok(())
+    }
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    edit(token::<type_permissions::And<Permissions, CanRead, CanWrite>>());
+    publish(token::<type_permissions::Or<Permissions, CanEditorGroup, CanAll>>());
+
+    let service = Service {
+        roles: [Permissions::Write].iter().cloned().collect(),
+    };
+    assert!(service.write().is_ok());
+}
@@ -0,0 +1,36 @@
+use type_permissions::requires;
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::Or;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+use type_permissions::TAnd;
+use type_permissions::TOr;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    CanRead,
+    CanWrite,
+}
+
+fn read_write<T: ?Sized + TAnd<Permissions, CanRead, CanWrite, O>, O>(_: PhantomToken<T>) {}
+
+fn read_or_write<T: ?Sized + TOr<Permissions, CanRead, CanWrite, O>, O>(_: PhantomToken<T>) {}
+
+#[requires("CanRead", "CanWrite")]
+fn edit() {}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    read_write(token::<And<Permissions, CanRead, CanWrite>>());
+    read_write(token::<And<Permissions, CanWrite, CanRead>>());
+
+    read_or_write(token::<Or<Permissions, CanRead, CanWrite>>());
+    read_or_write(token::<Or<Permissions, CanWrite, CanRead>>());
+
+    edit(token::<And<Permissions, CanRead, CanWrite>>());
+    edit(token::<And<Permissions, CanWrite, CanRead>>());
+}
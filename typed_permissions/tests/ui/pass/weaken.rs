@@ -0,0 +1,26 @@
+use type_permissions::And;
+use type_permissions::Dispatch;
+use type_permissions::PhantomToken;
+use type_permissions::Permissions;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone)]
+enum Permissions {
+    #[implies(CanWrite)]
+    CanAdmin,
+    CanRead,
+    CanWrite,
+}
+
+fn token<T: ?Sized + Dispatch<Permissions>>() -> PhantomToken<T> {
+    unsafe { PhantomToken::new_unchecked() }
+}
+
+fn main() {
+    let _: PhantomToken<CanRead> = token::<CanRead>().weaken::<CanRead, _>();
+    let _: PhantomToken<CanRead> = token::<And<Permissions, CanRead, CanWrite>>().weaken::<CanRead, _>();
+    let _: PhantomToken<CanWrite> = token::<And<Permissions, CanRead, CanWrite>>().weaken::<CanWrite, _>();
+    let _: PhantomToken<CanWrite> = token::<And<Permissions, CanRead, And<Permissions, CanAdmin, CanRead>>>()
+        .weaken::<CanAdmin, _>()
+        .weaken::<CanWrite, _>();
+    let _: PhantomToken<CanRead> = token::<All>().weaken::<CanRead, _>();
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "warp")]
+
+use std::collections::HashSet;
+
+use type_permissions::warp::require;
+use type_permissions::warp::PermissionRejection;
+use type_permissions::Permissions;
+use type_permissions::PhantomToken;
+use warp::Filter;
+
+#[derive(Permissions, Hash, Eq, PartialEq, Clone, Debug)]
+enum Permissions {
+    CanReadUsers,
+    CanDeleteUsers,
+}
+
+fn delete_users<T: ?Sized + TCanDeleteUsers>(_: PhantomToken<T>) -> &'static str {
+    "deleted"
+}
+
+// Reads the roles from a comma separated `x-roles` header, standing in for real auth.
+fn roles() -> impl Filter<Extract = (HashSet<Permissions>,), Error = warp::Rejection> + Clone {
+    warp::header::<String>("x-roles").map(|x: String| {
+        x.split(',')
+            .filter_map(|x| x.parse().ok())
+            .collect::<HashSet<_>>()
+    })
+}
+
+#[tokio::test]
+async fn require_admits_matching_roles() {
+    let filter = require::<Permissions, CanDeleteUsers, _>(roles());
+    let token = warp::test::request()
+        .header("x-roles", "CanReadUsers,CanDeleteUsers")
+        .filter(&filter)
+        .await;
+
+    assert_eq!(delete_users(token.unwrap()), "deleted");
+}
+
+#[tokio::test]
+async fn require_rejects_missing_roles() {
+    let filter = require::<Permissions, CanDeleteUsers, _>(roles());
+    let rejection = warp::test::request()
+        .header("x-roles", "CanReadUsers")
+        .filter(&filter)
+        .await
+        .unwrap_err();
+
+    assert!(rejection.find::<PermissionRejection>().is_some());
+}
+
+#[tokio::test]
+async fn require_composes_with_routes() {
+    let route = warp::path("admin")
+        .and(require::<Permissions, CanDeleteUsers, _>(roles()))
+        .map(delete_users);
+
+    let response = warp::test::request()
+        .path("/admin")
+        .header("x-roles", "CanDeleteUsers")
+        .reply(&route)
+        .await;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.body(), "deleted");
+
+    let response = warp::test::request()
+        .path("/admin")
+        .header("x-roles", "CanReadUsers")
+        .reply(&route)
+        .await;
+    assert_ne!(response.status(), 200);
+}